@@ -1,11 +1,15 @@
 import { Connection, PublicKey } from "@solana/web3.js";
 import { NextRequest, NextResponse } from "next/server";
-import { PROGRAM_ID } from "@/lib/constants";
+import {
+  PROGRAM_ID,
+  POST_DISCRIMINATOR,
+  PROFILE_DISCRIMINATOR,
+  PROFILE_BOT_FIELDS_MIN_SIZE,
+  PROFILE_PFP_MIN_SIZE,
+  hasDiscriminator,
+} from "@/lib/constants";
 const RPC_URL = process.env.NEXT_PUBLIC_RPC_URL || "https://viviyan-bkj12u-fast-mainnet.helius-rpc.com";
 
-// Post account size: 8 + 32 + (4+280) + 8 + 8 + 8 = 348
-const POST_SIZE = 348;
-
 interface PostData {
   address: string;
   author: string;
@@ -24,13 +28,11 @@ export async function GET(req: NextRequest) {
     // Fetch all program accounts
     const allAccounts = await connection.getProgramAccounts(PROGRAM_ID);
 
-    // Filter to post-sized accounts
-    const postAccounts = allAccounts.filter(a => a.account.data.length === POST_SIZE);
+    // Filter to Post accounts (any layout version)
+    const postAccounts = allAccounts.filter(a => hasDiscriminator(a.account.data, POST_DISCRIMINATOR));
 
     // Also fetch profiles for username lookup
-    const profileAccounts = allAccounts.filter(
-      a => a.account.data.length === 368 || a.account.data.length === 402 || a.account.data.length === 534
-    );
+    const profileAccounts = allAccounts.filter(a => hasDiscriminator(a.account.data, PROFILE_DISCRIMINATOR));
 
     // Build username map
     const usernameMap: Record<string, string> = {};
@@ -55,7 +57,7 @@ export async function GET(req: NextRequest) {
         offset += bioLen;
 
         let pfp = "";
-        if (data.length >= 534) {
+        if (data.length >= PROFILE_PFP_MIN_SIZE) {
           const pfpLen = data.readUInt32LE(offset);
           offset += 4;
           pfp = data.subarray(offset, offset + pfpLen).toString("utf-8");
@@ -64,7 +66,7 @@ export async function GET(req: NextRequest) {
 
         let accountType = "human";
         let verified = false;
-        if (data.length >= 402) {
+        if (data.length >= PROFILE_BOT_FIELDS_MIN_SIZE) {
           accountType = data[offset] === 1 ? "bot" : "human";
           offset += 1;
           offset += 32;
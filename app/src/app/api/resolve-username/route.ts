@@ -1,9 +1,8 @@
-import { PROGRAM_ID } from "@/lib/constants";
+import { PROGRAM_ID, PROFILE_DISCRIMINATOR_B58, PROFILE_PFP_MIN_SIZE } from "@/lib/constants";
 import { NextRequest, NextResponse } from "next/server";
 import { Connection, PublicKey } from "@solana/web3.js";
 
 const RPC_URL = process.env.NEXT_PUBLIC_RPC_URL || "https://viviyan-bkj12u-fast-mainnet.helius-rpc.com";
-const PROFILE_SIZES = [368, 402, 534];
 
 /**
  * Resolve a username to a wallet address (authority).
@@ -56,23 +55,15 @@ export async function GET(req: NextRequest) {
   // Fallback: scan on-chain profiles
   try {
     const connection = new Connection(RPC_URL, "confirmed");
-    const allAccounts = await connection.getProgramAccounts(PROGRAM_ID, {
-      filters: PROFILE_SIZES.map(size => ({ dataSize: size })).slice(0, 1),
+    // Profiles of every layout version share the Anchor discriminator
+    const accounts = await connection.getProgramAccounts(PROGRAM_ID, {
+      filters: [{ memcmp: { offset: 0, bytes: PROFILE_DISCRIMINATOR_B58 } }],
     });
 
-    // getProgramAccounts with multiple dataSize filters doesn't work — fetch all sizes
-    const accounts = [];
-    for (const size of PROFILE_SIZES) {
-      const batch = await connection.getProgramAccounts(PROGRAM_ID, {
-        filters: [{ dataSize: size }],
-      });
-      accounts.push(...batch);
-    }
-
     for (const { pubkey, account } of accounts) {
       const data = account.data;
       const size = data.length;
-      const isV3 = size === 534;
+      const isV3 = size >= PROFILE_PFP_MIN_SIZE;
       let offset = 8;
 
       const authority = new PublicKey(data.subarray(offset, offset + 32)).toBase58();
@@ -1,4 +1,11 @@
-import { PROGRAM_ID } from "@/lib/constants";
+import {
+  PROGRAM_ID,
+  POST_DISCRIMINATOR,
+  PROFILE_DISCRIMINATOR,
+  PROFILE_BOT_FIELDS_MIN_SIZE,
+  PROFILE_PFP_MIN_SIZE,
+  hasDiscriminator,
+} from "@/lib/constants";
 import { Connection, PublicKey } from "@solana/web3.js";
 import { NextResponse } from "next/server";
 import { getAllTld, findAllDomainsForTld } from "@onsol/tldparser";
@@ -21,13 +28,11 @@ export async function GET() {
       accountSizes: allAccounts.map(a => a.account.data.length),
     };
 
-    // Profile sizes: old=368, v2=402, v3=534 (with pfp)
-    const profiles = allAccounts.filter(
-      (a) => a.account.data.length === 368 || a.account.data.length === 402 || a.account.data.length === 534
-    );
+    // Profiles of every layout version
+    const profiles = allAccounts.filter((a) => hasDiscriminator(a.account.data, PROFILE_DISCRIMINATOR));
     
     // Other account sizes
-    const posts = allAccounts.filter((a) => a.account.data.length === 348);
+    const posts = allAccounts.filter((a) => hasDiscriminator(a.account.data, POST_DISCRIMINATOR));
     const follows = allAccounts.filter((a) => a.account.data.length === 80);
     const likes = allAccounts.filter((a) => a.account.data.length === 80);
 
@@ -63,9 +68,8 @@ export async function GET() {
     for (const { pubkey, account } of profiles) {
       try {
         const data = account.data;
-        const isV2 = data.length === 402; // v2: with account_type, bot_proof, verified
-        const isV3 = data.length === 534; // v3: v2 + pfp
-        const hasExtendedFields = isV2 || isV3;
+        const hasExtendedFields = data.length >= PROFILE_BOT_FIELDS_MIN_SIZE; // v2+: account_type, bot_proof, verified
+        const isV3 = data.length >= PROFILE_PFP_MIN_SIZE; // v3+: pfp
         let offset = 8; // Skip discriminator
 
         const authority = new PublicKey(data.subarray(offset, offset + 32));
@@ -1,4 +1,11 @@
-import { PROGRAM_ID } from "@/lib/constants";
+import {
+  PROGRAM_ID,
+  POST_DISCRIMINATOR,
+  PROFILE_DISCRIMINATOR,
+  PROFILE_BOT_FIELDS_MIN_SIZE,
+  PROFILE_PFP_MIN_SIZE,
+  hasDiscriminator,
+} from "@/lib/constants";
 import { NextRequest, NextResponse } from "next/server";
 import { Connection, PublicKey } from "@solana/web3.js";
 import { getDb, initSchema } from "@/lib/db";
@@ -8,8 +15,6 @@ const RPC_URL = process.env.NEXT_PUBLIC_RPC_URL || "https://viviyan-bkj12u-fast-
 const SYNC_SECRET = process.env.SYNC_SECRET || "";
 
 // Account sizes
-const PROFILE_SIZES = [368, 402, 534];
-const POST_SIZE = 348;
 const FOLLOW_SIZE = 80;
 
 /**
@@ -62,15 +67,15 @@ export async function POST(req: NextRequest) {
 
     // Process profiles
     const profileAccounts = allAccounts.filter(
-      (a) => PROFILE_SIZES.includes(a.account.data.length)
+      (a) => hasDiscriminator(a.account.data, PROFILE_DISCRIMINATOR)
     );
 
     for (const { pubkey, account } of profileAccounts) {
       try {
         const data = account.data;
         const size = data.length;
-        const isV2 = size === 402;
-        const isV3 = size === 534;
+        const hasBotFields = size >= PROFILE_BOT_FIELDS_MIN_SIZE;
+        const hasPfp = size >= PROFILE_PFP_MIN_SIZE;
         let offset = 8;
 
         const authority = new PublicKey(data.subarray(offset, offset + 32)).toBase58();
@@ -87,7 +92,7 @@ export async function POST(req: NextRequest) {
         offset += bioLen;
 
         let pfp = "";
-        if (isV3) {
+        if (hasPfp) {
           const pfpLen = data.readUInt32LE(offset);
           offset += 4;
           pfp = data.subarray(offset, offset + pfpLen).toString("utf-8");
@@ -96,7 +101,7 @@ export async function POST(req: NextRequest) {
 
         let accountType = "human";
         let verified = false;
-        if (isV2 || isV3) {
+        if (hasBotFields) {
           accountType = data[offset] === 1 ? "bot" : "human";
           offset += 1;
           offset += 32;
@@ -143,7 +148,7 @@ export async function POST(req: NextRequest) {
     }
 
     // Process posts
-    const postAccounts = allAccounts.filter((a) => a.account.data.length === POST_SIZE);
+    const postAccounts = allAccounts.filter((a) => hasDiscriminator(a.account.data, POST_DISCRIMINATOR));
 
     for (const { pubkey, account } of postAccounts) {
       try {
@@ -2,12 +2,17 @@ import { NextRequest, NextResponse } from "next/server";
 import { PublicKey } from "@solana/web3.js";
 import { getDb, initSchema } from "@/lib/db";
 
-import { PROGRAM_ID_STRING as PROGRAM_ID } from "@/lib/constants";
+import {
+  PROGRAM_ID_STRING as PROGRAM_ID,
+  POST_DISCRIMINATOR,
+  PROFILE_DISCRIMINATOR,
+  PROFILE_BOT_FIELDS_MIN_SIZE,
+  PROFILE_PFP_MIN_SIZE,
+  hasDiscriminator,
+} from "@/lib/constants";
 const WEBHOOK_SECRET = process.env.HELIUS_WEBHOOK_SECRET || "";
 
-// Account sizes for identification
-const PROFILE_SIZES = [368, 402, 534]; // v1, v2, v3
-const POST_SIZE = 348;
+// Account sizes for identification (Posts and Profiles are matched by discriminator)
 const FOLLOW_SIZE = 80; // same as Like size — disambiguate by seeds
 const REFERRAL_SIZE = 80;
 const REFERRER_STATS_SIZE = 48;
@@ -115,14 +120,14 @@ async function indexFromTransaction(tx: any, timestamp: number): Promise<number>
       continue;
     }
 
-    // Check if this belongs to our program by size matching
+    // Check if this belongs to our program by discriminator
     const size = buffer.length;
 
     try {
-      if (PROFILE_SIZES.includes(size)) {
+      if (hasDiscriminator(buffer, PROFILE_DISCRIMINATOR)) {
         await indexProfile(db, address, buffer, size, now);
         indexed++;
-      } else if (size === POST_SIZE) {
+      } else if (hasDiscriminator(buffer, POST_DISCRIMINATOR)) {
         await indexPost(db, address, buffer, now);
         indexed++;
       }
@@ -154,8 +159,8 @@ async function indexProfile(
   size: number,
   now: number,
 ) {
-  const isV2 = size === 402;
-  const isV3 = size === 534;
+  const hasBotFields = size >= PROFILE_BOT_FIELDS_MIN_SIZE;
+  const hasPfp = size >= PROFILE_PFP_MIN_SIZE;
   let offset = 8; // Skip discriminator
 
   const authority = new PublicKey(data.subarray(offset, offset + 32)).toBase58();
@@ -172,7 +177,7 @@ async function indexProfile(
   offset += bioLen;
 
   let pfp = "";
-  if (isV3) {
+  if (hasPfp) {
     const pfpLen = data.readUInt32LE(offset);
     offset += 4;
     pfp = data.subarray(offset, offset + pfpLen).toString("utf-8");
@@ -181,7 +186,7 @@ async function indexProfile(
 
   let accountType = "human";
   let verified = false;
-  if (isV2 || isV3) {
+  if (hasBotFields) {
     accountType = data[offset] === 1 ? "bot" : "human";
     offset += 1;
     offset += 32; // Skip bot_proof_hash
@@ -133,9 +133,9 @@ export default function ViewProfile() {
         offset += bioLen;
 
         let pfp = "";
-        // v3 format (534 bytes) has pfp field between bio and accountType
-        // v2 format (402 bytes) and v1 (<402 bytes) do not
-        const hasPfpField = data.length >= 534 || (data.length > 402 && data.length < 534);
+        // v3 and later (534 bytes and up) have a pfp field between bio and accountType;
+        // v2 (402 bytes) and v1 (368 bytes) do not
+        const hasPfpField = data.length >= 534;
         if (hasPfpField) {
           const pfpLen = data.readUInt32LE(offset);
           offset += 4;
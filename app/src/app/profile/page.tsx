@@ -237,7 +237,7 @@ export default function ProfilePage() {
       const bio = data.subarray(offset, offset + bioLen).toString("utf-8");
       offset += bioLen;
 
-      // Check for pfp field (v3 = 534 bytes)
+      // Check for pfp field (v3 and later, 534 bytes and up)
       let pfp = "";
       if (data.length >= 534) {
         const pfpLen = data.readUInt32LE(offset);
@@ -306,9 +306,15 @@ export default function ProfilePage() {
         Buffer.from(new Uint32Array([pfpBytes.length]).buffer), pfpBytes,
       ]);
 
+      const [usernamePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("username"), usernameBytes],
+        PROGRAM_ID
+      );
+
       const ix = new TransactionInstruction({
         keys: [
           { pubkey: profilePda, isSigner: false, isWritable: true },
+          { pubkey: usernamePda, isSigner: false, isWritable: true },
           { pubkey: publicKey, isSigner: true, isWritable: true },
           { pubkey: new PublicKey("11111111111111111111111111111111"), isSigner: false, isWritable: false },
         ],
@@ -369,11 +375,29 @@ export default function ProfilePage() {
         encodeOptionString(pfpChanged ? editPfp : null),
       ]);
 
+      const keys = [
+        { pubkey: profilePda, isSigner: false, isWritable: true },
+        { pubkey: publicKey, isSigner: true, isWritable: true },
+      ];
+      // Renaming moves the username registry entry: old name released, new name claimed
+      if (usernameChanged) {
+        keys.push(
+          {
+            pubkey: PublicKey.findProgramAddressSync([Buffer.from("username"), Buffer.from(profile.username)], PROGRAM_ID)[0],
+            isSigner: false,
+            isWritable: true,
+          },
+          {
+            pubkey: PublicKey.findProgramAddressSync([Buffer.from("username"), Buffer.from(editUsername)], PROGRAM_ID)[0],
+            isSigner: false,
+            isWritable: true,
+          },
+          { pubkey: new PublicKey("11111111111111111111111111111111"), isSigner: false, isWritable: false },
+        );
+      }
+
       const ix = new TransactionInstruction({
-        keys: [
-          { pubkey: profilePda, isSigner: false, isWritable: true },
-          { pubkey: publicKey, isSigner: true, isWritable: true },
-        ],
+        keys,
         programId: PROGRAM_ID,
         data: ixData,
       });
@@ -477,7 +501,7 @@ export default function ProfilePage() {
   }
 
   async function handleDeleteProfile() {
-    if (!publicKey || !signTransaction) return;
+    if (!publicKey || !signTransaction || !profile) return;
     if (!confirm("Are you sure? This will delete your profile and all data.")) return;
     setError(null);
 
@@ -487,11 +511,25 @@ export default function ProfilePage() {
         PROGRAM_ID
       );
 
+      // The profile's Username PDA is required; the name is released if this profile holds it
+      const [usernamePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("username"), Buffer.from(profile.username)],
+        PROGRAM_ID
+      );
+      // ProfileMetadata PDA, closed too if the profile has one
+      const [metadataPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("profile_metadata"), publicKey.toBuffer()],
+        PROGRAM_ID
+      );
+      const keys = [
+        { pubkey: profilePda, isSigner: false, isWritable: true },
+        { pubkey: publicKey, isSigner: true, isWritable: true },
+        { pubkey: usernamePda, isSigner: false, isWritable: true },
+        { pubkey: metadataPda, isSigner: false, isWritable: true },
+      ];
+
       const ix = new TransactionInstruction({
-        keys: [
-          { pubkey: profilePda, isSigner: false, isWritable: true },
-          { pubkey: publicKey, isSigner: true, isWritable: true },
-        ],
+        keys,
         programId: PROGRAM_ID,
         data: getCloseProfileDisc(),
       });
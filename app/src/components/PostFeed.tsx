@@ -66,11 +66,16 @@ export function PostFeed({ author }: { author?: string }) {
         [Buffer.from("like"), publicKey.toBuffer(), postPubkey.toBuffer()],
         PROGRAM_ID
       );
+      const [profilePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("profile"), publicKey.toBuffer()],
+        PROGRAM_ID
+      );
 
       const ix = new TransactionInstruction({
         keys: [
           { pubkey: likePda, isSigner: false, isWritable: true },
           { pubkey: postPubkey, isSigner: false, isWritable: true },
+          { pubkey: profilePda, isSigner: false, isWritable: false },
           { pubkey: publicKey, isSigner: true, isWritable: true },
           { pubkey: new PublicKey("11111111111111111111111111111111"), isSigner: false, isWritable: false },
         ],
@@ -181,9 +181,15 @@ export function RegisterProfile() {
         pfpBytes,
       ]);
       
+      const [usernamePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("username"), usernameBytes],
+        PROGRAM_ID
+      );
+
       const instruction = new TransactionInstruction({
         keys: [
           { pubkey: profilePda, isSigner: false, isWritable: true },
+          { pubkey: usernamePda, isSigner: false, isWritable: true },
           { pubkey: publicKey, isSigner: true, isWritable: true },
           { pubkey: new PublicKey("11111111111111111111111111111111"), isSigner: false, isWritable: false },
         ],
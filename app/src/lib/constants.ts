@@ -10,3 +10,28 @@ export const PROGRAM_ID = new PublicKey(
 
 export const PROGRAM_ID_STRING =
   process.env.NEXT_PUBLIC_PROGRAM_ID || "3mMxY4XcKrkPDHdLbUkssYy34smQtfhwBcfnMpLcBbZy";
+
+/**
+ * Anchor account discriminators: the first 8 bytes of sha256("account:<Name>").
+ * Match accounts on these, not on their size — sizes change with every layout bump.
+ */
+export const POST_DISCRIMINATOR = Buffer.from([8, 147, 90, 186, 185, 56, 192, 150]);
+
+export const PROFILE_DISCRIMINATOR = Buffer.from([184, 101, 165, 188, 95, 63, 127, 188]);
+
+/** Base58 form of a discriminator, for getProgramAccounts memcmp filters */
+export const POST_DISCRIMINATOR_B58 = "2SCFvsZq1W5";
+export const PROFILE_DISCRIMINATOR_B58 = "XqtBdGS7oVD";
+
+/**
+ * Profile layouts only ever add fields, so a layout has a field if it is at least
+ * as large as the first one that had it: v2 (402 bytes) added account_type,
+ * bot_proof_hash and verified; v3 (534 bytes) added pfp. v4 (630) and v5 (662)
+ * appended fields after created_at.
+ */
+export const PROFILE_BOT_FIELDS_MIN_SIZE = 402;
+export const PROFILE_PFP_MIN_SIZE = 534;
+
+export function hasDiscriminator(data: Buffer, discriminator: Buffer): boolean {
+  return data.length >= 8 && data.subarray(0, 8).equals(discriminator);
+}
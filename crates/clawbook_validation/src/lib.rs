@@ -0,0 +1,214 @@
+//! Text validation shared by the `clawbook` (Anchor) and `clawbook_pino` programs.
+//!
+//! Length rule: one character is one Unicode scalar value, i.e. what `str::chars`
+//! yields. Emoji built from several code points (ZWJ sequences, skin tones, flags)
+//! count as several characters. Fields also have a byte limit, since that is what
+//! the account space is sized for.
+#![no_std]
+
+/// Max username length. Usernames are ASCII, so bytes = characters.
+pub const MAX_USERNAME_LEN: usize = 32;
+
+/// Max bio length, in characters and in bytes.
+pub const MAX_BIO_LEN: usize = 256;
+
+/// Max profile picture URL length, in characters and in bytes.
+pub const MAX_PFP_LEN: usize = 128;
+
+/// Max post content length in characters.
+pub const MAX_CONTENT_CHARS: usize = 280;
+
+/// Max post content length in bytes — room for 280 characters of up to 4 bytes each,
+/// so the character limit is the one that applies. Posts are sized to their content.
+pub const MAX_CONTENT_BYTES: usize = 4 * MAX_CONTENT_CHARS;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ValidationError {
+    /// Over the field's character or byte limit
+    TooLong,
+    /// Not valid UTF-8
+    InvalidUtf8,
+    /// Contains a control character other than `\n`
+    ControlCharacter,
+    /// Contains a bidirectional override or isolate (U+202A–U+202E, U+2066–U+2069)
+    BidiControl,
+    /// Username is empty or contains characters outside `a-z`, `0-9` and `_`
+    InvalidUsername,
+}
+
+/// Number of characters in `text`, under the rule above.
+pub fn char_count(text: &str) -> usize {
+    text.chars().count()
+}
+
+/// Bidirectional embedding, override and isolate characters, which can make text
+/// display in a different order than it is stored.
+pub fn is_bidi_control(c: char) -> bool {
+    matches!(c, '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}')
+}
+
+/// Check free text (post content, bio, pfp URL): valid UTF-8, at most `max_bytes`
+/// bytes and `max_chars` characters, no control characters except `\n` and no
+/// bidi controls.
+pub fn validate_text(bytes: &[u8], max_chars: usize, max_bytes: usize) -> Result<&str, ValidationError> {
+    if bytes.len() > max_bytes {
+        return Err(ValidationError::TooLong);
+    }
+    let text = core::str::from_utf8(bytes).map_err(|_| ValidationError::InvalidUtf8)?;
+
+    let mut chars = 0;
+    for c in text.chars() {
+        if c.is_control() && c != '\n' {
+            return Err(ValidationError::ControlCharacter);
+        }
+        if is_bidi_control(c) {
+            return Err(ValidationError::BidiControl);
+        }
+        chars += 1;
+    }
+    if chars > max_chars {
+        return Err(ValidationError::TooLong);
+    }
+    Ok(text)
+}
+
+/// Check a username: 1-32 characters of `a-z`, `0-9` and `_`. Uppercase, look-alike
+/// and zero-width characters are all rejected, so each name has one spelling.
+pub fn validate_username(bytes: &[u8]) -> Result<&str, ValidationError> {
+    if bytes.len() > MAX_USERNAME_LEN {
+        return Err(ValidationError::TooLong);
+    }
+    if bytes.is_empty()
+        || !bytes
+            .iter()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || *b == b'_')
+    {
+        return Err(ValidationError::InvalidUsername);
+    }
+    core::str::from_utf8(bytes).map_err(|_| ValidationError::InvalidUtf8)
+}
+
+/// Check post content: at most 280 characters, see `validate_text`.
+pub fn validate_content(bytes: &[u8]) -> Result<&str, ValidationError> {
+    validate_text(bytes, MAX_CONTENT_CHARS, MAX_CONTENT_BYTES)
+}
+
+/// Check a bio: at most 256 characters and bytes, see `validate_text`.
+pub fn validate_bio(bytes: &[u8]) -> Result<&str, ValidationError> {
+    validate_text(bytes, MAX_BIO_LEN, MAX_BIO_LEN)
+}
+
+/// Check a profile picture URL: at most 128 characters and bytes, see `validate_text`.
+pub fn validate_pfp(bytes: &[u8]) -> Result<&str, ValidationError> {
+    validate_text(bytes, MAX_PFP_LEN, MAX_PFP_LEN)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    extern crate std;
+    use std::string::String;
+
+    fn repeat(c: char, n: usize) -> String {
+        core::iter::repeat_n(c, n).collect()
+    }
+
+    #[test]
+    fn rejects_invalid_utf8() {
+        assert_eq!(validate_content(&[0xff, 0xfe]), Err(ValidationError::InvalidUtf8));
+        // Truncated 4-byte sequence (start of 🦞)
+        assert_eq!(validate_content(&[0xf0, 0x9f, 0xa6]), Err(ValidationError::InvalidUtf8));
+        // Overlong encoding of '/'
+        assert_eq!(validate_bio(&[0xc0, 0xaf]), Err(ValidationError::InvalidUtf8));
+    }
+
+    #[test]
+    fn rejects_control_characters_except_newline() {
+        assert_eq!(validate_content(b"line one\nline two"), Ok("line one\nline two"));
+        for text in ["tab\there", "cr\rhere", "nul\0here", "bell\u{7}", "del\u{7f}", "c1\u{85}"] {
+            assert_eq!(
+                validate_content(text.as_bytes()),
+                Err(ValidationError::ControlCharacter),
+                "{text:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_bidi_overrides_and_isolates() {
+        for c in ['\u{202A}', '\u{202B}', '\u{202C}', '\u{202D}', '\u{202E}', '\u{2066}', '\u{2069}'] {
+            let mut text = String::from("abc");
+            text.push(c);
+            assert_eq!(validate_pfp(text.as_bytes()), Err(ValidationError::BidiControl), "{c:?}");
+        }
+        // Plain right-to-left text is fine
+        assert!(validate_bio("שלום".as_bytes()).is_ok());
+    }
+
+    #[test]
+    fn zero_width_characters() {
+        // Allowed in free text (ZWJ emoji sequences need them), each counts as a character
+        let family = "👨\u{200D}👩\u{200D}👧";
+        assert_eq!(validate_content(family.as_bytes()), Ok(family));
+        assert_eq!(char_count(family), 5);
+        // Never in usernames, where they would make look-alike names
+        assert_eq!(
+            validate_username("molty\u{200B}".as_bytes()),
+            Err(ValidationError::InvalidUsername)
+        );
+        assert_eq!(
+            validate_username("mol\u{200D}ty".as_bytes()),
+            Err(ValidationError::InvalidUsername)
+        );
+    }
+
+    #[test]
+    fn username_charset() {
+        assert_eq!(validate_username(b"molty_42"), Ok("molty_42"));
+        assert_eq!(validate_username(&[b'a'; MAX_USERNAME_LEN]), Ok(&*repeat('a', MAX_USERNAME_LEN)));
+        for name in ["", "Molty", "mol ty", "mol-ty", "mol.ty", "molty!", "mоlty"] {
+            assert_eq!(
+                validate_username(name.as_bytes()),
+                Err(ValidationError::InvalidUsername),
+                "{name:?}"
+            );
+        }
+        assert_eq!(
+            validate_username(&[b'a'; MAX_USERNAME_LEN + 1]),
+            Err(ValidationError::TooLong)
+        );
+    }
+
+    #[test]
+    fn content_is_limited_by_characters_not_bytes() {
+        // 280 four-byte characters fit; one more doesn't
+        let lobsters = repeat('🦞', MAX_CONTENT_CHARS);
+        assert_eq!(lobsters.len(), MAX_CONTENT_BYTES);
+        assert!(validate_content(lobsters.as_bytes()).is_ok());
+        let too_many = repeat('🦞', MAX_CONTENT_CHARS + 1);
+        assert_eq!(validate_content(too_many.as_bytes()), Err(ValidationError::TooLong));
+
+        // Same for one-byte characters
+        assert!(validate_content(repeat('a', MAX_CONTENT_CHARS).as_bytes()).is_ok());
+        assert_eq!(
+            validate_content(repeat('a', MAX_CONTENT_CHARS + 1).as_bytes()),
+            Err(ValidationError::TooLong)
+        );
+    }
+
+    #[test]
+    fn bio_and_pfp_are_limited_by_bytes_too() {
+        // 128 two-byte characters: within the character limit, over the byte limit
+        let accents = repeat('é', MAX_BIO_LEN / 2 + 1);
+        assert!(char_count(&accents) <= MAX_BIO_LEN);
+        assert_eq!(validate_bio(accents.as_bytes()), Err(ValidationError::TooLong));
+        assert!(validate_bio(repeat('é', MAX_BIO_LEN / 2).as_bytes()).is_ok());
+
+        assert!(validate_pfp(repeat('a', MAX_PFP_LEN).as_bytes()).is_ok());
+        assert_eq!(
+            validate_pfp(repeat('a', MAX_PFP_LEN + 1).as_bytes()),
+            Err(ValidationError::TooLong)
+        );
+    }
+}
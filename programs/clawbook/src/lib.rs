@@ -1,12 +1,24 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::{program::invoke, system_instruction};
+use anchor_lang::solana_program::{
+    program::{invoke, invoke_signed},
+    system_instruction,
+};
 use borsh::{BorshDeserialize, BorshSerialize};
+use clawbook_validation::{
+    validate_bio, validate_content, validate_pfp, validate_text, validate_username,
+    ValidationError,
+};
+use std::io::{Read, Write};
 use light_sdk::{
     account::LightAccount,
     address::v1::derive_address,
     cpi::{v1::{CpiAccounts, LightSystemProgramCpi}, InvokeLightSystemProgram, LightCpiInstruction},
     derive_light_cpi_signer,
-    instruction::{PackedAddressTreeInfo, ValidityProof},
+    hasher::{Hasher, Sha256},
+    instruction::{
+        account_meta::{CompressedAccountMeta, CompressedAccountMetaBurn},
+        PackedAddressTreeInfo, ValidityProof,
+    },
     CpiSigner, LightDiscriminator, PackedAddressTreeInfoExt,
 };
 
@@ -16,15 +28,71 @@ declare_id!("3mMxY4XcKrkPDHdLbUkssYy34smQtfhwBcfnMpLcBbZy");
 pub const LIGHT_CPI_SIGNER: CpiSigner =
     derive_light_cpi_signer!("3mMxY4XcKrkPDHdLbUkssYy34smQtfhwBcfnMpLcBbZy");
 
+/// Max posts per `create_compressed_posts` call — bounded by the transaction size
+/// (each post carries its content) and the combined proof size.
+pub const MAX_COMPRESSED_POSTS_PER_BATCH: usize = 4;
+
+/// Max bytes per article chunk — keeps `append_article_chunk` within one transaction.
+pub const MAX_ARTICLE_CHUNK_LEN: usize = 900;
+
+/// Max chunks per article — `finalize_article` must load every chunk in one transaction.
+pub const MAX_ARTICLE_CHUNKS: usize = 24;
+
+/// Max media attachments per post.
+pub const MAX_ATTACHMENTS: usize = 4;
+
+/// Seconds a boost stays active. The effective boost falls linearly to zero over
+/// this window; see `Post::effective_boost`.
+pub const BOOST_DURATION_SECS: i64 = 7 * 24 * 60 * 60;
+
+/// Smallest accepted boost (0.001 SOL).
+pub const MIN_BOOST_LAMPORTS: u64 = 1_000_000;
+
+/// Largest `crank_reward` an expiring post may carry (0.01 SOL).
+pub const MAX_CRANK_REWARD_LAMPORTS: u64 = 10_000_000;
+
+/// Max moderators per community, besides the creator.
+pub const MAX_MODERATORS: usize = 5;
+
+/// Max options per poll.
+pub const MAX_POLL_OPTIONS: usize = 8;
+
+/// Max pinned posts per profile.
+pub const MAX_PINNED_POSTS: usize = 3;
+
+/// Max hashtags per post.
+pub const MAX_TAGS: usize = 3;
+
+/// Max mentioned profiles per post — each one costs a Profile account and a compressed
+/// output in the same transaction.
+pub const MAX_MENTIONS: usize = 5;
+
+/// `SessionKey::permissions` bits — the instructions a session key may sign.
+pub const SESSION_POST: u8 = 1 << 0;
+pub const SESSION_LIKE: u8 = 1 << 1;
+pub const SESSION_FOLLOW: u8 = 1 << 2;
+pub const SESSION_REPLY: u8 = 1 << 3;
+pub const SESSION_ALL: u8 = SESSION_POST | SESSION_LIKE | SESSION_FOLLOW | SESSION_REPLY;
+
+/// Max entries in a ProfileMetadata account.
+pub const MAX_METADATA_ENTRIES: usize = 16;
+
+/// Max bytes per metadata key.
+pub const MAX_METADATA_KEY_LEN: usize = 32;
+
+/// Max bytes per text or URL metadata value.
+pub const MAX_METADATA_VALUE_LEN: usize = 256;
+
 #[program]
 pub mod clawbook {
     use super::*;
 
     /// Create a new profile for a human (via web UI)
     pub fn create_profile(ctx: Context<CreateProfile>, username: String, bio: String, pfp: String) -> Result<()> {
-        require!(username.len() <= 32, ClawbookError::UsernameTooLong);
-        require!(bio.len() <= 256, ClawbookError::BioTooLong);
-        require!(pfp.len() <= 128, ClawbookError::PfpTooLong);
+        validate_username(username.as_bytes())
+            .map_err(|e| validation_error(e, ClawbookError::UsernameTooLong))?;
+        validate_bio(bio.as_bytes()).map_err(|e| validation_error(e, ClawbookError::BioTooLong))?;
+        validate_pfp(pfp.as_bytes()).map_err(|e| validation_error(e, ClawbookError::PfpTooLong))?;
 
         let profile = &mut ctx.accounts.profile;
         profile.authority = ctx.accounts.authority.key();
@@ -38,6 +106,13 @@ pub mod clawbook {
         profile.follower_count = 0;
         profile.following_count = 0;
         profile.created_at = Clock::get()?.unix_timestamp;
+        profile.pinned_posts = [Pubkey::default(); MAX_PINNED_POSTS];
+        profile.controller = Pubkey::default();
+
+        let username_record = &mut ctx.accounts.username_record;
+        username_record.authority = profile.authority;
+        username_record.claimed_at = profile.created_at;
+        username_record.payer = ctx.accounts.authority.key();
         
         Ok(())
     }
@@ -50,9 +125,10 @@ pub mod clawbook {
         pfp: String,
         bot_proof_hash: [u8; 32],
     ) -> Result<()> {
-        require!(username.len() <= 32, ClawbookError::UsernameTooLong);
-        require!(bio.len() <= 256, ClawbookError::BioTooLong);
-        require!(pfp.len() <= 128, ClawbookError::PfpTooLong);
+        validate_username(username.as_bytes())
+            .map_err(|e| validation_error(e, ClawbookError::UsernameTooLong))?;
+        validate_bio(bio.as_bytes()).map_err(|e| validation_error(e, ClawbookError::BioTooLong))?;
+        validate_pfp(pfp.as_bytes()).map_err(|e| validation_error(e, ClawbookError::PfpTooLong))?;
         
         // Verify proof hash is not empty (actual verification happens off-chain)
         let empty_hash = [0u8; 32];
@@ -70,26 +146,61 @@ pub mod clawbook {
         profile.follower_count = 0;
         profile.following_count = 0;
         profile.created_at = Clock::get()?.unix_timestamp;
+        profile.pinned_posts = [Pubkey::default(); MAX_PINNED_POSTS];
+        profile.controller = Pubkey::default();
+
+        let username_record = &mut ctx.accounts.username_record;
+        username_record.authority = profile.authority;
+        username_record.claimed_at = profile.created_at;
+        username_record.payer = ctx.accounts.authority.key();
         
         Ok(())
     }
 
-    /// Create a new post
-    pub fn create_post(ctx: Context<CreatePost>, content: String) -> Result<()> {
-        require!(content.len() <= 280, ClawbookError::ContentTooLong);
-
-        let post = &mut ctx.accounts.post;
-        let profile = &mut ctx.accounts.profile;
-
-        post.author = ctx.accounts.authority.key();
-        post.content = content;
-        post.likes = 0;
-        post.created_at = Clock::get()?.unix_timestamp;
-        post.post_id = profile.post_count;
-
-        profile.post_count += 1;
+    /// Create a new text post. `create_post_with_options` adds attachments, mentions,
+    /// hashtags or an expiry.
+    pub fn create_post<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreatePost<'info>>,
+        content: String,
+    ) -> Result<()> {
+        let accounts = ctx.accounts;
+        publish_post(
+            &mut accounts.post,
+            &mut accounts.profile,
+            &accounts.authority,
+            &accounts.system_program,
+            ctx.remaining_accounts,
+            content,
+            PostOptions::default(),
+        )
+    }
 
-        Ok(())
+    /// Create a new post with attachments, mentions, hashtags and/or an expiry, set
+    /// through `options`; `PostOptions::default()` is the same as `create_post`.
+    ///
+    /// Mentions: the mentioned authorities' Profile PDAs go first in remaining_accounts
+    /// (same order as `mentions`), then one Topic PDA per tag, then the Light system
+    /// accounts. A CompressedMention is written per recipient.
+    /// Tags are normalized to lowercase ASCII and each one's Topic PDA is bumped
+    /// (created on first use, paid by the author).
+    /// Expiry: anyone can close the post with `expire_post` once `expires_at` passes.
+    /// `crank_reward` lamports are deposited into the post on top of rent and paid to
+    /// whoever expires it.
+    pub fn create_post_with_options<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreatePostWithOptions<'info>>,
+        content: String,
+        options: PostOptions,
+    ) -> Result<()> {
+        let accounts = ctx.accounts;
+        publish_post(
+            &mut accounts.post,
+            &mut accounts.profile,
+            &accounts.authority,
+            &accounts.system_program,
+            ctx.remaining_accounts,
+            content,
+            options,
+        )
     }
 
     /// Create a compressed post using ZK Compression (Light Protocol).
@@ -102,20 +213,73 @@ pub mod clawbook {
         output_tree_index: u8,
         content: String,
     ) -> Result<()> {
-        require!(content.len() <= 280, ClawbookError::ContentTooLong);
+        create_compressed_post_with_options(
+            ctx,
+            proof,
+            address_tree_info,
+            output_tree_index,
+            content,
+            PostOptions::default(),
+        )
+    }
+
+    /// `create_compressed_post` with attachments, mentions and hashtags. `options` work
+    /// as in `create_post_with_options`, except that compressed posts can't expire and
+    /// mentions are proven by `proof` (`mention_proof` is ignored). Topic PDAs are
+    /// created through the system program from the Light accounts.
+    pub fn create_compressed_post_with_options<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateCompressedPost<'info>>,
+        proof: ValidityProof,
+        address_tree_info: PackedAddressTreeInfo,
+        output_tree_index: u8,
+        content: String,
+        options: PostOptions,
+    ) -> Result<()> {
+        check_content(&content)?;
+        require!(
+            options.expires_at.is_none() && options.crank_reward == 0,
+            ClawbookError::InvalidExpiry
+        );
+        validate_attachments(&options.attachments)?;
+        let tags = normalize_tags(options.tags)?;
+        let mentions = options.mentions;
+        let remaining = authorize_signer(
+            &ctx.accounts.profile,
+            ctx.accounts.fee_payer.key,
+            ctx.remaining_accounts,
+            SESSION_POST,
+            1,
+        )?;
+        let topic_accounts = verify_mentions(&mentions, remaining)?;
+        require!(topic_accounts.len() >= tags.len(), ClawbookError::InvalidTopic);
+        let (topic_accounts, light_accounts) = topic_accounts.split_at(tags.len());
 
         let light_cpi_accounts = CpiAccounts::new(
             ctx.accounts.fee_payer.as_ref(),
-            ctx.remaining_accounts,
+            light_accounts,
             crate::LIGHT_CPI_SIGNER,
         );
 
+        let created_at = Clock::get()?.unix_timestamp;
+        if !tags.is_empty() {
+            let system_program = light_cpi_accounts
+                .system_program()
+                .map_err(|_| error!(ClawbookError::LightCpiError))?;
+            record_topics(
+                &tags,
+                topic_accounts,
+                ctx.accounts.fee_payer.as_ref(),
+                system_program,
+                created_at,
+            )?;
+        }
+
         // Derive a unique address for this compressed post using profile's post_count
         let post_count_bytes = ctx.accounts.profile.post_count.to_le_bytes();
         let (address, address_seed) = derive_address(
             &[
                 b"compressed_post",
-                ctx.accounts.fee_payer.key().as_ref(),
+                ctx.accounts.profile.authority.as_ref(),
                 &post_count_bytes,
             ],
             &address_tree_info
@@ -132,17 +296,184 @@ pub mod clawbook {
             output_tree_index,
         );
 
-        compressed_post.author = ctx.accounts.fee_payer.key();
+        let author = ctx.accounts.profile.authority;
+
+        compressed_post.author = author;
         compressed_post.content = content;
         compressed_post.likes = 0;
-        compressed_post.created_at = Clock::get()?.unix_timestamp;
+        compressed_post.created_at = created_at;
         compressed_post.post_id = ctx.accounts.profile.post_count;
+        compressed_post.attachments = options.attachments;
+        compressed_post.tags = tags;
+        compressed_post.signed_by = ctx.accounts.profile.post_signer(ctx.accounts.fee_payer.key);
 
         // Increment post count on the profile (shared counter for regular + compressed posts)
         let profile = &mut ctx.accounts.profile;
         profile.post_count += 1;
 
-        // CPI to Light System Program to create the compressed account
+        // CPI to Light System Program to create the compressed post and its mentions
+        let mut cpi = LightSystemProgramCpi::new_cpi(crate::LIGHT_CPI_SIGNER, proof)
+            .with_light_account(compressed_post)
+            .map_err(|_| error!(ClawbookError::LightCpiError))?;
+        let post_address = Pubkey::new_from_array(address);
+        for mention in new_mentions(&mentions, post_address, author, created_at, output_tree_index) {
+            cpi = cpi
+                .with_light_account(mention)
+                .map_err(|_| error!(ClawbookError::LightCpiError))?;
+        }
+        cpi.with_new_addresses(&[new_address_params])
+            .invoke(light_cpi_accounts)
+            .map_err(|_| error!(ClawbookError::LightCpiError))?;
+
+        Ok(())
+    }
+
+    /// Create several compressed posts in one instruction with a single validity proof
+    /// covering all new addresses. Addresses are derived from consecutive
+    /// `profile.post_count` values, exactly as N calls to `create_compressed_post` would.
+    pub fn create_compressed_posts<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateCompressedPost<'info>>,
+        proof: ValidityProof,
+        address_tree_info: PackedAddressTreeInfo,
+        output_tree_index: u8,
+        contents: Vec<String>,
+    ) -> Result<()> {
+        require!(
+            !contents.is_empty() && contents.len() <= MAX_COMPRESSED_POSTS_PER_BATCH,
+            ClawbookError::InvalidBatchSize
+        );
+        for content in contents.iter() {
+            check_content(content)?;
+        }
+        let light_accounts = authorize_signer(
+            &ctx.accounts.profile,
+            ctx.accounts.fee_payer.key,
+            ctx.remaining_accounts,
+            SESSION_POST,
+            contents.len() as u32,
+        )?;
+
+        let light_cpi_accounts = CpiAccounts::new(
+            ctx.accounts.fee_payer.as_ref(),
+            light_accounts,
+            crate::LIGHT_CPI_SIGNER,
+        );
+        let address_tree_pubkey = address_tree_info
+            .get_tree_pubkey(&light_cpi_accounts)
+            .map_err(|_| error!(ClawbookError::LightCpiError))?;
+
+        let author = ctx.accounts.profile.authority;
+        let created_at = Clock::get()?.unix_timestamp;
+        let profile = &mut ctx.accounts.profile;
+
+        let mut cpi = LightSystemProgramCpi::new_cpi(crate::LIGHT_CPI_SIGNER, proof);
+        let mut new_address_params = Vec::with_capacity(contents.len());
+
+        for content in contents {
+            let post_count_bytes = profile.post_count.to_le_bytes();
+            let (address, address_seed) = derive_address(
+                &[b"compressed_post", author.as_ref(), &post_count_bytes],
+                &address_tree_pubkey,
+                &crate::ID,
+            );
+            new_address_params.push(address_tree_info.into_new_address_params_packed(address_seed));
+
+            let mut compressed_post = LightAccount::<CompressedPost>::new_init(
+                &crate::ID,
+                Some(address),
+                output_tree_index,
+            );
+            compressed_post.author = author;
+            compressed_post.content = content;
+            compressed_post.likes = 0;
+            compressed_post.created_at = created_at;
+            compressed_post.post_id = profile.post_count;
+            compressed_post.signed_by = profile.post_signer(ctx.accounts.fee_payer.key);
+
+            cpi = cpi
+                .with_light_account(compressed_post)
+                .map_err(|_| error!(ClawbookError::LightCpiError))?;
+
+            profile.post_count += 1;
+        }
+
+        cpi.with_new_addresses(&new_address_params)
+            .invoke(light_cpi_accounts)
+            .map_err(|_| error!(ClawbookError::LightCpiError))?;
+
+        Ok(())
+    }
+
+    /// Convert a regular post into a compressed post (only the author). The compressed
+    /// address uses the same seeds as `create_compressed_post` with the post's original
+    /// `post_id`, so clients find it the same way. The Post PDA is closed, its rent
+    /// returned to the author and a PostTombstone left behind as in `close_post`, so
+    /// likers can reclaim their Like rent with `unlike_deleted_post`.
+    pub fn compress_post<'info>(
+        ctx: Context<'_, '_, '_, 'info, CompressPost<'info>>,
+        proof: ValidityProof,
+        address_tree_info: PackedAddressTreeInfo,
+        output_tree_index: u8,
+    ) -> Result<()> {
+        let light_accounts = authorize_profile_signer(
+            &ctx.accounts.profile,
+            ctx.accounts.authority.key,
+            ctx.remaining_accounts,
+            MemberRole::Admin,
+        )?;
+
+        let light_cpi_accounts = CpiAccounts::new(
+            ctx.accounts.authority.as_ref(),
+            light_accounts,
+            crate::LIGHT_CPI_SIGNER,
+        );
+
+        let post = &ctx.accounts.post;
+        let post_id_bytes = post.post_id.to_le_bytes();
+        let (address, address_seed) = derive_address(
+            &[
+                b"compressed_post",
+                ctx.accounts.profile.authority.as_ref(),
+                &post_id_bytes,
+            ],
+            &address_tree_info
+                .get_tree_pubkey(&light_cpi_accounts)
+                .map_err(|_| error!(ClawbookError::LightCpiError))?,
+            &crate::ID,
+        );
+        let new_address_params = address_tree_info.into_new_address_params_packed(address_seed);
+
+        let mut compressed_post = LightAccount::<CompressedPost>::new_init(
+            &crate::ID,
+            Some(address),
+            output_tree_index,
+        );
+
+        compressed_post.author = post.author;
+        compressed_post.content = post.content.clone();
+        compressed_post.likes = post.likes;
+        compressed_post.created_at = post.created_at;
+        compressed_post.post_id = post.post_id;
+        compressed_post.parent = post.parent;
+        compressed_post.root = post.root;
+        compressed_post.reply_count = post.reply_count;
+        compressed_post.revision = post.revision;
+        compressed_post.edited_at = post.edited_at;
+        compressed_post.history_hash = post.history_hash;
+        compressed_post.attachments = post.attachments.clone();
+        compressed_post.tags = post.tags.clone();
+        compressed_post.repost_count = post.repost_count;
+        compressed_post.quoted_post = post.quoted_post;
+        compressed_post.quote_count = post.quote_count;
+        compressed_post.compressed_from = post.key();
+        compressed_post.signed_by = post.signed_by;
+
+        let tombstone = &mut ctx.accounts.tombstone;
+        tombstone.post = post.key();
+        tombstone.author = post.author;
+        tombstone.post_id = post.post_id;
+        tombstone.deleted_at = Clock::get()?.unix_timestamp;
+
         LightSystemProgramCpi::new_cpi(crate::LIGHT_CPI_SIGNER, proof)
             .with_light_account(compressed_post)
             .map_err(|_| error!(ClawbookError::LightCpiError))?
@@ -150,16 +481,252 @@ pub mod clawbook {
             .invoke(light_cpi_accounts)
             .map_err(|_| error!(ClawbookError::LightCpiError))?;
 
+        // Post closed via close = authority constraint, rent returned to the author
+        Ok(())
+    }
+
+    /// Reply to a regular post. The reply is a new `Post` that links to its parent
+    /// and the thread root; the parent's `reply_count` is incremented.
+    pub fn reply_to_post<'info>(
+        ctx: Context<'_, '_, '_, 'info, ReplyToPost<'info>>,
+        content: String,
+    ) -> Result<()> {
+        check_content(&content)?;
+        authorize_signer(
+            &ctx.accounts.profile,
+            ctx.accounts.authority.key,
+            ctx.remaining_accounts,
+            SESSION_REPLY,
+            1,
+        )?;
+
+        let parent_key = ctx.accounts.parent.key();
+        let parent = &mut ctx.accounts.parent;
+        let post = &mut ctx.accounts.post;
+        let profile = &mut ctx.accounts.profile;
+
+        post.author = profile.authority;
+        post.content = content;
+        post.likes = 0;
+        post.created_at = Clock::get()?.unix_timestamp;
+        post.post_id = profile.post_count;
+        post.parent = parent_key;
+        // A top-level parent starts the thread, otherwise inherit the parent's root
+        post.root = if parent.root == Pubkey::default() { parent_key } else { parent.root };
+        post.signed_by = profile.post_signer(ctx.accounts.authority.key);
+
+        parent.reply_count += 1;
+        profile.post_count += 1;
+
+        Ok(())
+    }
+
+    /// Reply to a compressed post. The reply is a regular `Post`; the compressed
+    /// parent's `reply_count` is bumped through the Light System Program.
+    pub fn reply_to_compressed_post<'info>(
+        ctx: Context<'_, '_, '_, 'info, ReplyToCompressedPost<'info>>,
+        proof: ValidityProof,
+        parent_meta: CompressedAccountMeta,
+        parent: CompressedPost,
+        content: String,
+    ) -> Result<()> {
+        check_content(&content)?;
+        let light_accounts = authorize_signer(
+            &ctx.accounts.profile,
+            ctx.accounts.authority.key,
+            ctx.remaining_accounts,
+            SESSION_REPLY,
+            1,
+        )?;
+
+        let light_cpi_accounts = CpiAccounts::new(
+            ctx.accounts.authority.as_ref(),
+            light_accounts,
+            crate::LIGHT_CPI_SIGNER,
+        );
+
+        let parent_key = Pubkey::new_from_array(parent_meta.address);
+        let root = if parent.root == Pubkey::default() { parent_key } else { parent.root };
+
+        // The system program re-hashes `parent` against the tree, so the data can't be forged
+        let mut compressed_parent = LightAccount::<CompressedPost>::new_mut(
+            &crate::ID,
+            &parent_meta,
+            parent,
+        )
+        .map_err(|_| error!(ClawbookError::LightCpiError))?;
+        compressed_parent.reply_count += 1;
+
+        let post = &mut ctx.accounts.post;
+        let profile = &mut ctx.accounts.profile;
+
+        post.author = profile.authority;
+        post.content = content;
+        post.likes = 0;
+        post.created_at = Clock::get()?.unix_timestamp;
+        post.post_id = profile.post_count;
+        post.parent = parent_key;
+        post.root = root;
+        post.signed_by = profile.post_signer(ctx.accounts.authority.key);
+
+        profile.post_count += 1;
+
+        LightSystemProgramCpi::new_cpi(crate::LIGHT_CPI_SIGNER, proof)
+            .with_light_account(compressed_parent)
+            .map_err(|_| error!(ClawbookError::LightCpiError))?
+            .invoke(light_cpi_accounts)
+            .map_err(|_| error!(ClawbookError::LightCpiError))?;
+
+        Ok(())
+    }
+
+    /// Start a long-form article. The author declares the total length, chunk count
+    /// and sha256 of the full body up front; `finalize_article` checks the appended
+    /// chunks against them. Shares the `profile.post_count` ID space with posts.
+    pub fn start_article(
+        ctx: Context<StartArticle>,
+        title: String,
+        total_len: u32,
+        chunk_count: u16,
+        body_hash: [u8; 32],
+    ) -> Result<()> {
+        require!(title.len() <= 128, ClawbookError::TitleTooLong);
+        require!(
+            chunk_count > 0
+                && chunk_count as usize <= MAX_ARTICLE_CHUNKS
+                && total_len as usize <= chunk_count as usize * MAX_ARTICLE_CHUNK_LEN,
+            ClawbookError::ArticleTooLong
+        );
+
+        let article = &mut ctx.accounts.article;
+        let profile = &mut ctx.accounts.profile;
+
+        article.author = profile.authority;
+        article.article_id = profile.post_count;
+        article.title = title;
+        article.total_len = total_len;
+        article.chunk_count = chunk_count;
+        article.chunks_written = 0;
+        article.bytes_written = 0;
+        article.body_hash = body_hash;
+        article.finalized = false;
+        article.created_at = Clock::get()?.unix_timestamp;
+        article.finalized_at = 0;
+
+        profile.post_count += 1;
+
+        Ok(())
+    }
+
+    /// Append the next chunk of an article. Chunks are append-only and must be
+    /// written in order (`index == chunks_written`).
+    pub fn append_article_chunk(
+        ctx: Context<AppendArticleChunk>,
+        index: u16,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        let article = &mut ctx.accounts.article;
+        require!(!article.finalized, ClawbookError::ArticleFinalized);
+        require!(
+            index == article.chunks_written
+                && index < article.chunk_count
+                && !data.is_empty()
+                && data.len() <= MAX_ARTICLE_CHUNK_LEN
+                && article.bytes_written as usize + data.len() <= article.total_len as usize,
+            ClawbookError::InvalidArticleChunk
+        );
+
+        let chunk = &mut ctx.accounts.chunk;
+        chunk.article = article.key();
+        chunk.index = index;
+        article.bytes_written += data.len() as u32;
+        chunk.data = data;
+
+        article.chunks_written += 1;
+
+        Ok(())
+    }
+
+    /// Finalize an article once every chunk is written. All chunk accounts are passed
+    /// in order via remaining_accounts and their concatenation must hash to
+    /// `body_hash`. A finalized article can't be appended to or changed.
+    pub fn finalize_article<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FinalizeArticle<'info>>,
+    ) -> Result<()> {
+        let article_key = ctx.accounts.article.key();
+        let article = &mut ctx.accounts.article;
+        require!(!article.finalized, ClawbookError::ArticleFinalized);
+        require!(
+            article.chunks_written == article.chunk_count
+                && article.bytes_written == article.total_len,
+            ClawbookError::ArticleIncomplete
+        );
+        require!(
+            ctx.remaining_accounts.len() == article.chunk_count as usize,
+            ClawbookError::ArticleIncomplete
+        );
+
+        let mut chunks = Vec::with_capacity(ctx.remaining_accounts.len());
+        for (i, chunk_ai) in ctx.remaining_accounts.iter().enumerate() {
+            let chunk = Account::<ArticleChunk>::try_from(chunk_ai)?;
+            require!(
+                chunk.article == article_key && chunk.index as usize == i,
+                ClawbookError::InvalidArticleChunk
+            );
+            chunks.push(chunk);
+        }
+
+        let parts: Vec<&[u8]> = chunks.iter().map(|chunk| chunk.data.as_slice()).collect();
+        let body_hash = Sha256::hashv(&parts).map_err(|_| error!(ClawbookError::HashError))?;
+        require!(body_hash == article.body_hash, ClawbookError::ArticleHashMismatch);
+
+        article.finalized = true;
+        article.finalized_at = Clock::get()?.unix_timestamp;
+
+        Ok(())
+    }
+
+    /// Abandon an article that was never finalized (only the author). Every chunk
+    /// written so far is passed in order via remaining_accounts and closed along with
+    /// the Article; rent returned. The article's id is not reused.
+    pub fn abort_article<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AbortArticle<'info>>,
+    ) -> Result<()> {
+        let article_key = ctx.accounts.article.key();
+        let article = &ctx.accounts.article;
+        require!(!article.finalized, ClawbookError::ArticleFinalized);
+        require!(
+            ctx.remaining_accounts.len() == article.chunks_written as usize,
+            ClawbookError::InvalidArticleChunk
+        );
+
+        for (i, chunk_ai) in ctx.remaining_accounts.iter().enumerate() {
+            let chunk = Account::<ArticleChunk>::try_from(chunk_ai)?;
+            require!(
+                chunk.article == article_key && chunk.index as usize == i,
+                ClawbookError::InvalidArticleChunk
+            );
+            chunk.close(ctx.accounts.authority.to_account_info())?;
+        }
+
+        // Article closed via close = authority constraint
         Ok(())
     }
 
     /// Follow another profile
-    pub fn follow(ctx: Context<Follow>) -> Result<()> {
+    pub fn follow<'info>(ctx: Context<'_, '_, '_, 'info, Follow<'info>>) -> Result<()> {
+        authorize_signer(
+            &ctx.accounts.follower_profile,
+            ctx.accounts.authority.key,
+            ctx.remaining_accounts,
+            SESSION_FOLLOW,
+            1,
+        )?;
         let follow_account = &mut ctx.accounts.follow_account;
         let follower_profile = &mut ctx.accounts.follower_profile;
         let following_profile = &mut ctx.accounts.following_profile;
 
-        follow_account.follower = ctx.accounts.authority.key();
+        follow_account.follower = follower_profile.authority;
         follow_account.following = following_profile.authority;
         follow_account.created_at = Clock::get()?.unix_timestamp;
 
@@ -170,7 +737,13 @@ pub mod clawbook {
     }
 
     /// Unfollow a profile
-    pub fn unfollow(ctx: Context<Unfollow>) -> Result<()> {
+    pub fn unfollow<'info>(ctx: Context<'_, '_, '_, 'info, Unfollow<'info>>) -> Result<()> {
+        authorize_profile_signer(
+            &ctx.accounts.follower_profile,
+            ctx.accounts.authority.key,
+            ctx.remaining_accounts,
+            MemberRole::Editor,
+        )?;
         let follower_profile = &mut ctx.accounts.follower_profile;
         let following_profile = &mut ctx.accounts.following_profile;
 
@@ -181,12 +754,41 @@ pub mod clawbook {
         Ok(())
     }
 
+    /// Follow a hashtag topic
+    pub fn follow_topic(ctx: Context<FollowTopic>) -> Result<()> {
+        let topic_follow = &mut ctx.accounts.topic_follow;
+        let topic = &mut ctx.accounts.topic;
+
+        topic_follow.follower = ctx.accounts.follower_profile.authority;
+        topic_follow.topic = topic.key();
+        topic_follow.created_at = Clock::get()?.unix_timestamp;
+
+        topic.follower_count += 1;
+
+        Ok(())
+    }
+
+    /// Unfollow a hashtag topic
+    pub fn unfollow_topic(ctx: Context<UnfollowTopic>) -> Result<()> {
+        let topic = &mut ctx.accounts.topic;
+        topic.follower_count = topic.follower_count.saturating_sub(1);
+
+        Ok(())
+    }
+
     /// Like a post
-    pub fn like_post(ctx: Context<LikePost>) -> Result<()> {
+    pub fn like_post<'info>(ctx: Context<'_, '_, '_, 'info, LikePost<'info>>) -> Result<()> {
+        authorize_signer(
+            &ctx.accounts.profile,
+            ctx.accounts.authority.key,
+            ctx.remaining_accounts,
+            SESSION_LIKE,
+            1,
+        )?;
         let like = &mut ctx.accounts.like;
         let post = &mut ctx.accounts.post;
 
-        like.user = ctx.accounts.authority.key();
+        like.user = ctx.accounts.profile.authority;
         like.post = post.key();
         like.created_at = Clock::get()?.unix_timestamp;
 
@@ -196,300 +798,4287 @@ pub mod clawbook {
     }
 
     /// Unlike a post
-    pub fn unlike_post(ctx: Context<UnlikePost>) -> Result<()> {
+    pub fn unlike_post<'info>(ctx: Context<'_, '_, '_, 'info, UnlikePost<'info>>) -> Result<()> {
+        authorize_profile_signer(
+            &ctx.accounts.profile,
+            ctx.accounts.authority.key,
+            ctx.remaining_accounts,
+            MemberRole::Editor,
+        )?;
         let post = &mut ctx.accounts.post;
         // Use saturating_sub to prevent underflow if likes is already 0
         post.likes = post.likes.saturating_sub(1);
         Ok(())
     }
 
-    /// Close/delete a profile (only authority can close their own profile)
-    pub fn close_profile(_ctx: Context<CloseProfile>) -> Result<()> {
-        // Account closed via close = authority constraint, rent returned to authority
-        Ok(())
-    }
-
-    /// Record a referral — called after profile creation when user has a referral code.
-    /// Creates the referral link and increments referrer's stats.
-    pub fn record_referral(ctx: Context<RecordReferral>) -> Result<()> {
-        let referral = &mut ctx.accounts.referral;
-        referral.referred = ctx.accounts.authority.key();
-        referral.referrer = ctx.accounts.referrer_profile.authority;
-        referral.created_at = Clock::get()?.unix_timestamp;
-
-        let referrer_stats = &mut ctx.accounts.referrer_stats;
-        referrer_stats.authority = ctx.accounts.referrer_profile.authority;
-        referrer_stats.referral_count += 1;
-
-        Ok(())
-    }
-
-    /// Update profile username, bio, and/or pfp
-    pub fn update_profile(
-        ctx: Context<UpdateProfile>,
-        username: Option<String>,
-        bio: Option<String>,
-        pfp: Option<String>,
+    /// React to a post, or switch your existing reaction to a different kind. The
+    /// Reaction lives at the Like PDA address, so each user has one like or reaction
+    /// per post; a Like made with `like_post` is converted in place. `ReactionKind::Like`
+    /// counts towards `post.likes`, other kinds towards `post.reaction_counts`.
+    pub fn react<'info>(
+        ctx: Context<'_, '_, '_, 'info, React<'info>>,
+        kind: ReactionKind,
     ) -> Result<()> {
-        let profile = &mut ctx.accounts.profile;
+        authorize_profile_signer(
+            &ctx.accounts.profile,
+            ctx.accounts.authority.key,
+            ctx.remaining_accounts,
+            MemberRole::Editor,
+        )?;
+        let reaction_ai = ctx.accounts.reaction.to_account_info();
+        let authority_ai = ctx.accounts.authority.to_account_info();
+        let system_program_ai = ctx.accounts.system_program.to_account_info();
+        let user = ctx.accounts.profile.authority;
+        let post_key = ctx.accounts.post.key();
+        let now = Clock::get()?.unix_timestamp;
 
-        if let Some(new_username) = username {
-            require!(new_username.len() <= 32, ClawbookError::UsernameTooLong);
-            profile.username = new_username;
-        }
+        let previous = if reaction_ai.data_is_empty() {
+            create_pda_account(
+                &authority_ai,
+                &reaction_ai,
+                &system_program_ai,
+                Reaction::SPACE,
+                &[b"like", user.as_ref(), post_key.as_ref(), &[ctx.bumps.reaction]],
+            )?;
+            None
+        } else {
+            require!(*reaction_ai.owner == crate::ID, ClawbookError::InvalidLikeRecord);
+            let data = reaction_ai.try_borrow_data()?;
+            if data.starts_with(Reaction::DISCRIMINATOR) {
+                Some(Reaction::try_deserialize(&mut &data[..])?.kind)
+            } else if data.starts_with(Like::DISCRIMINATOR) {
+                Some(ReactionKind::Like)
+            } else {
+                return err!(ClawbookError::InvalidLikeRecord);
+            }
+        };
 
-        if let Some(new_bio) = bio {
-            require!(new_bio.len() <= 256, ClawbookError::BioTooLong);
-            profile.bio = new_bio;
+        let post = &mut ctx.accounts.post;
+        if let Some(previous) = previous {
+            if previous == kind {
+                return Ok(());
+            }
+            let count = post.reaction_count_mut(previous);
+            *count = count.saturating_sub(1);
         }
+        *post.reaction_count_mut(kind) += 1;
 
-        if let Some(new_pfp) = pfp {
-            require!(new_pfp.len() <= 128, ClawbookError::PfpTooLong);
-            profile.pfp = new_pfp;
+        // A Like from like_post is 80 bytes; grow it to fit the kind
+        if reaction_ai.data_len() < Reaction::SPACE {
+            let new_min_balance = Rent::get()?.minimum_balance(Reaction::SPACE);
+            let current_lamports = reaction_ai.lamports();
+            if new_min_balance > current_lamports {
+                invoke(
+                    &system_instruction::transfer(
+                        authority_ai.key,
+                        reaction_ai.key,
+                        new_min_balance - current_lamports,
+                    ),
+                    &[authority_ai, reaction_ai.clone(), system_program_ai],
+                )?;
+            }
+            reaction_ai.resize(Reaction::SPACE)?;
         }
 
+        let reaction = Reaction {
+            user,
+            post: post_key,
+            kind,
+            created_at: now,
+        };
+        reaction.try_serialize(&mut &mut reaction_ai.try_borrow_mut_data()?[..])?;
+
         Ok(())
     }
 
-    /// Claim a bot — links a passkey-verified human wallet to a bot profile.
-    /// One human can only claim one bot; one bot can only be claimed once.
-    pub fn claim_bot(ctx: Context<ClaimBot>) -> Result<()> {
-        let bot_profile = &ctx.accounts.bot_profile;
-        require!(bot_profile.account_type == AccountType::Bot, ClawbookError::InvalidBotProfile);
-
-        let bot_claim = &mut ctx.accounts.bot_claim;
-        bot_claim.owner = ctx.accounts.owner.key();
-        bot_claim.bot = bot_profile.authority;
-        bot_claim.claimed_at = Clock::get()?.unix_timestamp;
+    /// Remove your reaction from a post. Closes the Reaction PDA (rent returned).
+    pub fn unreact<'info>(ctx: Context<'_, '_, '_, 'info, Unreact<'info>>) -> Result<()> {
+        authorize_profile_signer(
+            &ctx.accounts.profile,
+            ctx.accounts.authority.key,
+            ctx.remaining_accounts,
+            MemberRole::Editor,
+        )?;
+        let kind = ctx.accounts.reaction.kind;
+        let count = ctx.accounts.post.reaction_count_mut(kind);
+        // Use saturating_sub to prevent underflow if the count is already 0
+        *count = count.saturating_sub(1);
+        Ok(())
+    }
 
-        let human_claim = &mut ctx.accounts.human_claim;
-        human_claim.owner = ctx.accounts.owner.key();
-        human_claim.bot = bot_profile.authority;
-        human_claim.claimed_at = Clock::get()?.unix_timestamp;
+    /// Like a compressed post. The like is recorded as a CompressedLike at an address
+    /// derived from (user, post), so a user can't like the same post twice.
+    /// Pass `closed_like_meta` to re-like a post whose like record was closed by
+    /// `unlike_compressed_post` (compressed addresses can't be created twice).
+    /// For a post made by `compress_post`, the user's Like PDA for the original post
+    /// goes first in remaining_accounts; a like it still holds is already counted.
+    pub fn like_compressed_post<'info>(
+        ctx: Context<'_, '_, '_, 'info, LikeCompressedPost<'info>>,
+        proof: ValidityProof,
+        post_meta: CompressedAccountMeta,
+        post: CompressedPost,
+        like_address_tree_info: PackedAddressTreeInfo,
+        like_output_tree_index: u8,
+        closed_like_meta: Option<CompressedAccountMeta>,
+    ) -> Result<()> {
+        let light_accounts =
+            check_legacy_like(&post, &ctx.accounts.profile.authority, ctx.remaining_accounts)?;
+        let light_cpi_accounts = CpiAccounts::new(
+            ctx.accounts.authority.as_ref(),
+            light_accounts,
+            crate::LIGHT_CPI_SIGNER,
+        );
+
+        let (like_address, like_address_seed) = derive_address(
+            &[
+                b"compressed_like",
+                ctx.accounts.profile.authority.as_ref(),
+                &post_meta.address,
+            ],
+            &like_address_tree_info
+                .get_tree_pubkey(&light_cpi_accounts)
+                .map_err(|_| error!(ClawbookError::LightCpiError))?,
+            &crate::ID,
+        );
+
+        let mut compressed_post = LightAccount::<CompressedPost>::new_mut(
+            &crate::ID,
+            &post_meta,
+            post,
+        )
+        .map_err(|_| error!(ClawbookError::LightCpiError))?;
+        compressed_post.likes += 1;
+
+        let mut new_addresses = Vec::with_capacity(1);
+        let mut compressed_like = match closed_like_meta {
+            Some(like_meta) => {
+                require!(like_meta.address == like_address, ClawbookError::InvalidLikeRecord);
+                // Input must be the empty account left by unlike — fails if the like is still active
+                LightAccount::<CompressedLike>::new_empty(&crate::ID, &like_meta)
+                    .map_err(|_| error!(ClawbookError::LightCpiError))?
+            }
+            None => {
+                new_addresses.push(
+                    like_address_tree_info.into_new_address_params_packed(like_address_seed),
+                );
+                LightAccount::<CompressedLike>::new_init(
+                    &crate::ID,
+                    Some(like_address),
+                    like_output_tree_index,
+                )
+            }
+        };
+        compressed_like.user = ctx.accounts.profile.authority;
+        compressed_like.post = Pubkey::new_from_array(post_meta.address);
+        compressed_like.created_at = Clock::get()?.unix_timestamp;
+
+        LightSystemProgramCpi::new_cpi(crate::LIGHT_CPI_SIGNER, proof)
+            .with_light_account(compressed_post)
+            .map_err(|_| error!(ClawbookError::LightCpiError))?
+            .with_light_account(compressed_like)
+            .map_err(|_| error!(ClawbookError::LightCpiError))?
+            .with_new_addresses(&new_addresses)
+            .invoke(light_cpi_accounts)
+            .map_err(|_| error!(ClawbookError::LightCpiError))?;
 
         Ok(())
     }
 
-    /// Migrate old profile (v2, 402 bytes, no pfp field) to new format (v3, 534 bytes, with pfp).
-    /// Old profiles cause OOM when deserialized with the new schema because byte offsets shift.
-    /// This instruction reads raw bytes, reallocs, and inserts an empty pfp field.
-    /// Safe to call on already-migrated profiles (no-op).
-    pub fn migrate_profile(ctx: Context<MigrateProfile>) -> Result<()> {
-        let profile_ai = ctx.accounts.profile.to_account_info();
-        let old_size = profile_ai.data_len();
+    /// Unlike a compressed post. Closes the CompressedLike record and decrements `likes`.
+    pub fn unlike_compressed_post<'info>(
+        ctx: Context<'_, '_, '_, 'info, UnlikeCompressedPost<'info>>,
+        proof: ValidityProof,
+        post_meta: CompressedAccountMeta,
+        post: CompressedPost,
+        like_meta: CompressedAccountMeta,
+        like: CompressedLike,
+    ) -> Result<()> {
+        require!(
+            like.user == ctx.accounts.profile.authority
+                && like.post == Pubkey::new_from_array(post_meta.address),
+            ClawbookError::InvalidLikeRecord
+        );
 
-        // Already new format — nothing to do
-        if old_size >= 534 {
-            return Ok(());
-        }
+        let light_cpi_accounts = CpiAccounts::new(
+            ctx.accounts.authority.as_ref(),
+            ctx.remaining_accounts,
+            crate::LIGHT_CPI_SIGNER,
+        );
+
+        let mut compressed_post = LightAccount::<CompressedPost>::new_mut(
+            &crate::ID,
+            &post_meta,
+            post,
+        )
+        .map_err(|_| error!(ClawbookError::LightCpiError))?;
+        // Use saturating_sub to prevent underflow if likes is already 0
+        compressed_post.likes = compressed_post.likes.saturating_sub(1);
+
+        let compressed_like = LightAccount::<CompressedLike>::new_close(
+            &crate::ID,
+            &like_meta,
+            like,
+        )
+        .map_err(|_| error!(ClawbookError::LightCpiError))?;
+
+        LightSystemProgramCpi::new_cpi(crate::LIGHT_CPI_SIGNER, proof)
+            .with_light_account(compressed_post)
+            .map_err(|_| error!(ClawbookError::LightCpiError))?
+            .with_light_account(compressed_like)
+            .map_err(|_| error!(ClawbookError::LightCpiError))?
+            .invoke(light_cpi_accounts)
+            .map_err(|_| error!(ClawbookError::LightCpiError))?;
+
+        Ok(())
+    }
+
+    /// Edit a compressed post's content (only the author). Same revision and
+    /// `history_hash` rules as `edit_post`.
+    pub fn update_compressed_post<'info>(
+        ctx: Context<'_, '_, '_, 'info, UpdateCompressedPost<'info>>,
+        proof: ValidityProof,
+        account_meta: CompressedAccountMeta,
+        post: CompressedPost,
+        content: String,
+    ) -> Result<()> {
+        check_content(&content)?;
+        require!(post.author == ctx.accounts.profile.authority, ClawbookError::NotPostAuthor);
 
-        // Only handle known old format (402 bytes)
-        require!(old_size == 402, ClawbookError::InvalidProfile);
+        let light_accounts = authorize_profile_signer(
+            &ctx.accounts.profile,
+            ctx.accounts.authority.key,
+            ctx.remaining_accounts,
+            edit_role(post.signed_by, ctx.accounts.authority.key),
+        )?;
 
-        // --- 1. Read old data and find insert point ---
-        let old_data = profile_ai.try_borrow_data()?.to_vec();
+        let light_cpi_accounts = CpiAccounts::new(
+            ctx.accounts.authority.as_ref(),
+            light_accounts,
+            crate::LIGHT_CPI_SIGNER,
+        );
 
-        // Old layout after 8-byte discriminator:
-        //   authority(32) + username(4+N) + bio(4+M) + account_type(1) +
-        //   bot_proof_hash(32) + verified(1) + post_count(8) +
-        //   follower_count(8) + following_count(8) + created_at(8)
-        // We insert pfp(4+0) between bio and account_type.
+        let mut compressed_post = LightAccount::<CompressedPost>::new_mut(
+            &crate::ID,
+            &account_meta,
+            post,
+        )
+        .map_err(|_| error!(ClawbookError::LightCpiError))?;
 
-        let mut cursor: usize = 8 + 32; // skip discriminator + authority
+        compressed_post.history_hash =
+            fold_content_history(&compressed_post.history_hash, &compressed_post.content)?;
+        compressed_post.content = content;
+        compressed_post.revision += 1;
+        compressed_post.edited_at = Clock::get()?.unix_timestamp;
 
-        // Skip username (borsh string: 4-byte len prefix + data)
-        let uname_len = u32::from_le_bytes(
-            old_data[cursor..cursor + 4].try_into().unwrap(),
-        ) as usize;
-        cursor += 4 + uname_len;
+        LightSystemProgramCpi::new_cpi(crate::LIGHT_CPI_SIGNER, proof)
+            .with_light_account(compressed_post)
+            .map_err(|_| error!(ClawbookError::LightCpiError))?
+            .invoke(light_cpi_accounts)
+            .map_err(|_| error!(ClawbookError::LightCpiError))?;
 
-        // Skip bio
-        let bio_len = u32::from_le_bytes(
-            old_data[cursor..cursor + 4].try_into().unwrap(),
-        ) as usize;
-        cursor += 4 + bio_len;
+        Ok(())
+    }
 
-        let insert_point = cursor;
+    /// Delete a compressed post (only the author). The account's data is removed but
+    /// its address stays in the address tree, which marks the post as deleted.
+    pub fn close_compressed_post<'info>(
+        ctx: Context<'_, '_, '_, 'info, CloseCompressedPost<'info>>,
+        proof: ValidityProof,
+        account_meta: CompressedAccountMeta,
+        post: CompressedPost,
+    ) -> Result<()> {
+        require!(post.author == ctx.accounts.profile.authority, ClawbookError::NotPostAuthor);
 
-        // Remaining serialized fields: 1 + 32 + 1 + 8 + 8 + 8 + 8 = 66 bytes
-        // (account_type + bot_proof_hash + verified + post_count + follower + following + created_at)
-        const TAIL_LEN: usize = 1 + 32 + 1 + 8 + 8 + 8 + 8; // 66
-        let tail = old_data[insert_point..insert_point + TAIL_LEN].to_vec();
+        let light_accounts = authorize_profile_signer(
+            &ctx.accounts.profile,
+            ctx.accounts.authority.key,
+            ctx.remaining_accounts,
+            MemberRole::Admin,
+        )?;
 
-        // Release borrow before realloc
-        drop(old_data);
+        let light_cpi_accounts = CpiAccounts::new(
+            ctx.accounts.authority.as_ref(),
+            light_accounts,
+            crate::LIGHT_CPI_SIGNER,
+        );
 
-        // --- 2. Transfer additional rent for the larger account ---
-        let new_size: usize = 534;
-        let rent = Rent::get()?;
-        let new_min_balance = rent.minimum_balance(new_size);
-        let current_lamports = profile_ai.lamports();
+        let compressed_post = LightAccount::<CompressedPost>::new_close(
+            &crate::ID,
+            &account_meta,
+            post,
+        )
+        .map_err(|_| error!(ClawbookError::LightCpiError))?;
 
-        if new_min_balance > current_lamports {
-            let diff = new_min_balance - current_lamports;
-            invoke(
-                &system_instruction::transfer(
-                    ctx.accounts.authority.key,
-                    profile_ai.key,
-                    diff,
-                ),
-                &[
-                    ctx.accounts.authority.to_account_info(),
-                    profile_ai.clone(),
-                    ctx.accounts.system_program.to_account_info(),
-                ],
-            )?;
+        LightSystemProgramCpi::new_cpi(crate::LIGHT_CPI_SIGNER, proof)
+            .with_light_account(compressed_post)
+            .map_err(|_| error!(ClawbookError::LightCpiError))?
+            .invoke(light_cpi_accounts)
+            .map_err(|_| error!(ClawbookError::LightCpiError))?;
+
+        Ok(())
+    }
+
+    /// Edit a post's content (only the author). The old content is folded into
+    /// `history_hash` = sha256(history_hash || sha256(old_content)), so anyone holding
+    /// the previous versions can prove what the post said before.
+    pub fn edit_post<'info>(
+        ctx: Context<'_, '_, '_, 'info, EditPost<'info>>,
+        content: String,
+    ) -> Result<()> {
+        check_content(&content)?;
+        authorize_profile_signer(
+            &ctx.accounts.profile,
+            ctx.accounts.authority.key,
+            ctx.remaining_accounts,
+            edit_role(ctx.accounts.post.signed_by, ctx.accounts.authority.key),
+        )?;
+
+        // The account was resized to fit `content`; zero it so any tail stays zero
+        // (see `Post`). The whole account is re-serialized on exit.
+        {
+            let post_ai = ctx.accounts.post.to_account_info();
+            let mut data = post_ai.try_borrow_mut_data()?;
+            data[8..].fill(0);
         }
 
-        // --- 3. Resize and rewrite data ---
-        profile_ai.resize(new_size)?;
+        let post = &mut ctx.accounts.post;
+        post.history_hash = fold_content_history(&post.history_hash, &post.content)?;
+        post.content = content;
+        post.revision += 1;
+        post.edited_at = Clock::get()?.unix_timestamp;
+
+        Ok(())
+    }
 
-        let mut data = profile_ai.try_borrow_mut_data()?;
+    /// Close an expired post. Permissionless: the caller gets the post's crank reward
+    /// and the rest (rent) goes back to the author (see `Post::rent_receiver`).
+    pub fn expire_post(ctx: Context<ExpirePost>) -> Result<()> {
+        let post = &ctx.accounts.post;
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            post.expires_at != 0 && now >= post.expires_at,
+            ClawbookError::PostNotExpired
+        );
 
-        // Insert empty pfp string: length prefix = 0 (4 bytes)
-        data[insert_point..insert_point + 4].copy_from_slice(&0u32.to_le_bytes());
+        let tombstone = &mut ctx.accounts.tombstone;
+        tombstone.post = post.key();
+        tombstone.author = post.author;
+        tombstone.post_id = post.post_id;
+        tombstone.deleted_at = now;
 
-        // Write tail (account_type through created_at) right after pfp
-        data[insert_point + 4..insert_point + 4 + TAIL_LEN].copy_from_slice(&tail);
+        // The caller fronts the tombstone's rent; it comes back out of the post's rent,
+        // as it would have had the author closed the post
+        let tombstone_rent = ctx.accounts.tombstone.get_lamports();
+        let reward = ctx.accounts.post.crank_reward;
+        ctx.accounts.post.sub_lamports(tombstone_rent + reward)?;
+        ctx.accounts.caller.add_lamports(tombstone_rent + reward)?;
 
-        // Zero-fill any remaining bytes
-        for byte in data[insert_point + 4 + TAIL_LEN..new_size].iter_mut() {
-            *byte = 0;
+        // Post closed via close = author constraint, remaining rent returned to the author
+        Ok(())
+    }
+
+    /// Delete a post (only the author). The Post PDA is closed and its rent returned;
+    /// a small PostTombstone PDA is left behind so indexers and Like holders can tell
+    /// the post was deleted. `profile.post_count` is not decremented, so post seeds
+    /// are never reused.
+    pub fn close_post<'info>(ctx: Context<'_, '_, '_, 'info, ClosePost<'info>>) -> Result<()> {
+        authorize_profile_signer(
+            &ctx.accounts.profile,
+            ctx.accounts.authority.key,
+            ctx.remaining_accounts,
+            MemberRole::Admin,
+        )?;
+
+        let post = &ctx.accounts.post;
+        let tombstone = &mut ctx.accounts.tombstone;
+
+        tombstone.post = post.key();
+        tombstone.author = post.author;
+        tombstone.post_id = post.post_id;
+        tombstone.deleted_at = Clock::get()?.unix_timestamp;
+
+        Ok(())
+    }
+
+    /// Remove a like from a deleted post. Closes the Like PDA (rent returned).
+    pub fn unlike_deleted_post<'info>(
+        ctx: Context<'_, '_, '_, 'info, UnlikeDeletedPost<'info>>,
+    ) -> Result<()> {
+        authorize_profile_signer(
+            &ctx.accounts.profile,
+            ctx.accounts.authority.key,
+            ctx.remaining_accounts,
+            MemberRole::Editor,
+        )?;
+        // Like closed via close = authority constraint; the tombstone proves the post is gone
+        Ok(())
+    }
+
+    /// Remove a reaction from a deleted post. Closes the Reaction PDA (rent returned).
+    pub fn unreact_deleted_post<'info>(
+        ctx: Context<'_, '_, '_, 'info, UnreactDeletedPost<'info>>,
+    ) -> Result<()> {
+        authorize_profile_signer(
+            &ctx.accounts.profile,
+            ctx.accounts.authority.key,
+            ctx.remaining_accounts,
+            MemberRole::Editor,
+        )?;
+        // Reaction closed via close = authority constraint; the tombstone proves the post is gone
+        Ok(())
+    }
+
+    /// Repost another post. The RepostAccount PDA is seeded by reposter + post,
+    /// so the same post can only be reposted once per user.
+    pub fn repost<'info>(ctx: Context<'_, '_, '_, 'info, Repost<'info>>) -> Result<()> {
+        authorize_profile_signer(
+            &ctx.accounts.profile,
+            ctx.accounts.authority.key,
+            ctx.remaining_accounts,
+            MemberRole::Editor,
+        )?;
+        let repost = &mut ctx.accounts.repost;
+        let post = &mut ctx.accounts.post;
+
+        repost.reposter = ctx.accounts.profile.authority;
+        repost.post = post.key();
+        repost.created_at = Clock::get()?.unix_timestamp;
+
+        post.repost_count += 1;
+
+        Ok(())
+    }
+
+    /// Undo a repost. Closes the RepostAccount PDA (rent returned).
+    pub fn undo_repost<'info>(ctx: Context<'_, '_, '_, 'info, UndoRepost<'info>>) -> Result<()> {
+        authorize_profile_signer(
+            &ctx.accounts.profile,
+            ctx.accounts.authority.key,
+            ctx.remaining_accounts,
+            MemberRole::Editor,
+        )?;
+        let post = &mut ctx.accounts.post;
+        // Use saturating_sub to prevent underflow if repost_count is already 0
+        post.repost_count = post.repost_count.saturating_sub(1);
+        Ok(())
+    }
+
+    /// Quote another post — a new post with its own content that references the quoted post
+    pub fn quote_post<'info>(
+        ctx: Context<'_, '_, '_, 'info, QuotePost<'info>>,
+        content: String,
+    ) -> Result<()> {
+        check_content(&content)?;
+        authorize_profile_signer(
+            &ctx.accounts.profile,
+            ctx.accounts.authority.key,
+            ctx.remaining_accounts,
+            MemberRole::Editor,
+        )?;
+
+        let quoted_key = ctx.accounts.quoted_post.key();
+        let quoted_post = &mut ctx.accounts.quoted_post;
+        let post = &mut ctx.accounts.post;
+        let profile = &mut ctx.accounts.profile;
+
+        post.author = profile.authority;
+        post.content = content;
+        post.likes = 0;
+        post.created_at = Clock::get()?.unix_timestamp;
+        post.post_id = profile.post_count;
+        post.quoted_post = quoted_key;
+        post.signed_by = profile.post_signer(ctx.accounts.authority.key);
+
+        quoted_post.quote_count += 1;
+        profile.post_count += 1;
+
+        Ok(())
+    }
+
+    /// Create the treasury that collects boost payments. Only the program's upgrade
+    /// authority can call this, once.
+    pub fn initialize_treasury(ctx: Context<InitializeTreasury>, admin: Pubkey) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.admin = admin;
+        treasury.total_boosted = 0;
+        treasury.total_withdrawn = 0;
+        Ok(())
+    }
+
+    /// Hand the treasury over to a new admin (only the current admin)
+    pub fn set_treasury_admin(ctx: Context<TreasuryAdmin>, admin: Pubkey) -> Result<()> {
+        ctx.accounts.treasury.admin = admin;
+        Ok(())
+    }
+
+    /// Withdraw boost payments from the treasury (only the admin). The treasury
+    /// always keeps its rent-exempt minimum.
+    pub fn withdraw_treasury(ctx: Context<TreasuryAdmin>, amount: u64) -> Result<()> {
+        let treasury_ai = ctx.accounts.treasury.to_account_info();
+        let min_balance = Rent::get()?.minimum_balance(treasury_ai.data_len());
+        require!(
+            treasury_ai.lamports().saturating_sub(min_balance) >= amount,
+            ClawbookError::InsufficientTreasuryBalance
+        );
+
+        treasury_ai.sub_lamports(amount)?;
+        ctx.accounts.admin.add_lamports(amount)?;
+        ctx.accounts.treasury.total_withdrawn += amount;
+
+        Ok(())
+    }
+
+    /// Boost a post. Pays `lamports` into the treasury, decays the post's current
+    /// boost to now, adds `lamports` to it and restarts the 7-day window. Every
+    /// boost is added to the booster's BoostReceipt for the post.
+    pub fn boost_post(ctx: Context<BoostPost>, lamports: u64) -> Result<()> {
+        require!(lamports >= MIN_BOOST_LAMPORTS, ClawbookError::BoostTooSmall);
+
+        invoke(
+            &system_instruction::transfer(
+                ctx.accounts.authority.key,
+                &ctx.accounts.treasury.key(),
+                lamports,
+            ),
+            &[
+                ctx.accounts.authority.to_account_info(),
+                ctx.accounts.treasury.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+        )?;
+
+        let now = Clock::get()?.unix_timestamp;
+        let post = &mut ctx.accounts.post;
+        let receipt = &mut ctx.accounts.receipt;
+
+        post.boost_score = post.effective_boost(now).saturating_add(lamports);
+        post.boost_expires_at = now + BOOST_DURATION_SECS;
+
+        let stats = &mut ctx.accounts.stats;
+        if stats.boost_count == 0 {
+            stats.booster = ctx.accounts.authority.key();
+            stats.first_boosted_at = now;
+        }
+        if receipt.boost_count == 0 {
+            receipt.booster = ctx.accounts.authority.key();
+            receipt.post = post.key();
+            receipt.first_boosted_at = now;
+            stats.posts_boosted += 1;
         }
+        receipt.total_lamports += lamports;
+        receipt.boost_count += 1;
+        receipt.last_boosted_at = now;
+        stats.total_lamports += lamports;
+        stats.boost_count += 1;
+        stats.last_boosted_at = now;
+
+        ctx.accounts.treasury.total_boosted += lamports;
 
         Ok(())
     }
-}
 
-// === Account Type Enum ===
+    /// Create a community. `slug` (1-32 chars of a-z, 0-9, _ or -) is its unique
+    /// handle. The creator becomes its first member and is always a moderator.
+    pub fn create_community(
+        ctx: Context<CreateCommunity>,
+        slug: String,
+        description: String,
+        join_policy: JoinPolicy,
+    ) -> Result<()> {
+        require!(
+            !slug.is_empty()
+                && slug.len() <= 32
+                && slug.bytes().all(|b| {
+                    b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'_' || b == b'-'
+                }),
+            ClawbookError::InvalidCommunitySlug
+        );
+        validate_bio(description.as_bytes())
+            .map_err(|e| validation_error(e, ClawbookError::DescriptionTooLong))?;
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
-pub enum AccountType {
-    #[default]
-    Human = 0,
-    Bot = 1,
-}
+        let now = Clock::get()?.unix_timestamp;
+        let creator = ctx.accounts.profile.authority;
+        let community = &mut ctx.accounts.community;
+        let membership = &mut ctx.accounts.membership;
 
-// === Account Structures ===
+        community.slug = slug;
+        community.description = description;
+        community.creator = creator;
+        community.moderators = Vec::new();
+        community.join_policy = join_policy;
+        community.post_count = 0;
+        community.member_count = 1;
+        community.created_at = now;
 
-#[account]
-pub struct Profile {
-    pub authority: Pubkey,          // 32 bytes
-    pub username: String,           // 4 + 32 bytes
-    pub bio: String,                // 4 + 256 bytes
-    pub pfp: String,                // 4 + 128 bytes (URL/IPFS/Arweave)
-    pub account_type: AccountType,  // 1 byte
-    pub bot_proof_hash: [u8; 32],   // 32 bytes (SHA256 of proof)
-    pub verified: bool,             // 1 byte
-    pub post_count: u64,            // 8 bytes
-    pub follower_count: u64,        // 8 bytes
-    pub following_count: u64,       // 8 bytes
-    pub created_at: i64,            // 8 bytes
-}
+        membership.community = community.key();
+        membership.member = creator;
+        membership.approved = true;
+        membership.joined_at = now;
+        membership.payer = ctx.accounts.authority.key();
 
-// Profile space: 8 + 32 + (4+32) + (4+256) + (4+128) + 1 + 32 + 1 + 8 + 8 + 8 + 8 = 534 bytes
+        Ok(())
+    }
 
-#[account]
-pub struct Post {
-    pub author: Pubkey,             // 32 bytes
-    pub content: String,            // 4 + 280 bytes
-    pub likes: u64,                 // 8 bytes
-    pub created_at: i64,            // 8 bytes
-    pub post_id: u64,               // 8 bytes
-}
+    /// Replace a community's moderator set (only the creator). Max 5.
+    pub fn set_moderators(ctx: Context<SetModerators>, moderators: Vec<Pubkey>) -> Result<()> {
+        require!(moderators.len() <= MAX_MODERATORS, ClawbookError::TooManyModerators);
+        ctx.accounts.community.moderators = moderators;
+        Ok(())
+    }
 
-#[account]
-pub struct FollowAccount {
-    pub follower: Pubkey,           // 32 bytes
-    pub following: Pubkey,          // 32 bytes
-    pub created_at: i64,            // 8 bytes
-}
+    /// Join a community. Open communities approve the membership right away;
+    /// otherwise it waits for a moderator's `approve_member`.
+    pub fn join_community<'info>(
+        ctx: Context<'_, '_, '_, 'info, JoinCommunity<'info>>,
+    ) -> Result<()> {
+        authorize_profile_signer(
+            &ctx.accounts.profile,
+            ctx.accounts.authority.key,
+            ctx.remaining_accounts,
+            MemberRole::Editor,
+        )?;
+        let community = &mut ctx.accounts.community;
+        let membership = &mut ctx.accounts.membership;
 
-#[account]
-pub struct Like {
-    pub user: Pubkey,               // 32 bytes
-    pub post: Pubkey,               // 32 bytes
-    pub created_at: i64,            // 8 bytes
-}
+        membership.community = community.key();
+        membership.member = ctx.accounts.profile.authority;
+        membership.approved = community.join_policy == JoinPolicy::Open;
+        membership.joined_at = Clock::get()?.unix_timestamp;
+        membership.payer = ctx.accounts.authority.key();
 
-#[account]
-pub struct Referral {
-    pub referred: Pubkey,           // 32 bytes — who was referred
-    pub referrer: Pubkey,           // 32 bytes — who referred them
-    pub created_at: i64,            // 8 bytes
-}
+        if membership.approved {
+            community.member_count += 1;
+        }
 
-// Referral space: 8 + 32 + 32 + 8 = 80 bytes
+        Ok(())
+    }
 
-#[account]
-pub struct ReferrerStats {
-    pub authority: Pubkey,          // 32 bytes — the referrer
+    /// Approve a pending membership (moderators only)
+    pub fn approve_member(ctx: Context<ApproveMember>) -> Result<()> {
+        let membership = &mut ctx.accounts.membership;
+        require!(!membership.approved, ClawbookError::AlreadyApproved);
+
+        membership.approved = true;
+        ctx.accounts.community.member_count += 1;
+
+        Ok(())
+    }
+
+    /// Leave a community. Closes Membership PDA (rent returned).
+    pub fn leave_community<'info>(
+        ctx: Context<'_, '_, '_, 'info, LeaveCommunity<'info>>,
+    ) -> Result<()> {
+        authorize_profile_signer(
+            &ctx.accounts.profile,
+            ctx.accounts.authority.key,
+            ctx.remaining_accounts,
+            MemberRole::Editor,
+        )?;
+        if ctx.accounts.membership.approved {
+            let community = &mut ctx.accounts.community;
+            community.member_count = community.member_count.saturating_sub(1);
+        }
+        Ok(())
+    }
+
+    /// Remove a member, or reject a pending one (moderators only; moderators themselves
+    /// can't be removed). Closes the Membership PDA; rent goes back to whoever paid for
+    /// it. Works after the member's profile is closed.
+    pub fn remove_member(ctx: Context<RemoveMember>) -> Result<()> {
+        let community = &mut ctx.accounts.community;
+        let membership = &ctx.accounts.membership;
+        require!(!community.is_moderator(&membership.member), ClawbookError::MemberIsModerator);
+
+        if membership.approved {
+            community.member_count = community.member_count.saturating_sub(1);
+        }
+        Ok(())
+    }
+
+    /// Ban a profile authority from a community (moderators only). Creates the Ban PDA
+    /// that `join_community` checks, and closes its Membership if it has one. The
+    /// profile doesn't need to exist.
+    pub fn ban_member(ctx: Context<BanMember>, member: Pubkey) -> Result<()> {
+        let community = &mut ctx.accounts.community;
+        require!(!community.is_moderator(&member), ClawbookError::MemberIsModerator);
+
+        let membership = ctx.accounts.membership.to_account_info();
+        if !membership.data_is_empty() {
+            let entry = Membership::try_deserialize(&mut &membership.try_borrow_data()?[..])?;
+            require_keys_eq!(
+                entry.payer,
+                ctx.accounts.membership_payer.key(),
+                ClawbookError::InvalidRentReceiver
+            );
+            if entry.approved {
+                community.member_count = community.member_count.saturating_sub(1);
+            }
+            close_program_account(&membership, &ctx.accounts.membership_payer)?;
+        }
+
+        let ban = &mut ctx.accounts.ban;
+        ban.community = community.key();
+        ban.member = member;
+        ban.banned_by = ctx.accounts.profile.authority;
+        ban.banned_at = Clock::get()?.unix_timestamp;
+
+        Ok(())
+    }
+
+    /// Lift a ban (moderators only). Closes the Ban PDA; the profile can join again.
+    pub fn unban_member(_ctx: Context<UnbanMember>) -> Result<()> {
+        Ok(())
+    }
+
+    /// Create a post in a community. Only approved members can post. The post uses the
+    /// author's usual Post PDA and `profile.post_count`, with `community` set. Accepts
+    /// session keys and organization members like `create_post`.
+    pub fn create_community_post<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateCommunityPost<'info>>,
+        content: String,
+    ) -> Result<()> {
+        check_content(&content)?;
+        authorize_signer(
+            &ctx.accounts.profile,
+            ctx.accounts.authority.key,
+            ctx.remaining_accounts,
+            SESSION_POST,
+            1,
+        )?;
+
+        let community = &mut ctx.accounts.community;
+        let post = &mut ctx.accounts.post;
+        let profile = &mut ctx.accounts.profile;
+
+        post.author = profile.authority;
+        post.content = content;
+        post.likes = 0;
+        post.created_at = Clock::get()?.unix_timestamp;
+        post.post_id = profile.post_count;
+        post.community = community.key();
+        post.signed_by = profile.post_signer(ctx.accounts.authority.key);
+
+        profile.post_count += 1;
+        community.post_count += 1;
+
+        Ok(())
+    }
+
+    /// Hide a community post (moderators only). The post stays on-chain; clients
+    /// should not show hidden posts in the community feed.
+    pub fn hide_community_post(ctx: Context<ModerateCommunityPost>) -> Result<()> {
+        ctx.accounts.post.hidden = true;
+        Ok(())
+    }
+
+    /// Unhide a community post (moderators only)
+    pub fn unhide_community_post(ctx: Context<ModerateCommunityPost>) -> Result<()> {
+        ctx.accounts.post.hidden = false;
+        Ok(())
+    }
+
+    /// Attach a poll to one of your own posts. 2-8 options of up to 50 chars each.
+    /// `closes_at` is an optional unix timestamp after which votes are rejected.
+    pub fn create_poll(
+        ctx: Context<CreatePoll>,
+        options: Vec<String>,
+        closes_at: Option<i64>,
+    ) -> Result<()> {
+        require!(
+            (2..=MAX_POLL_OPTIONS).contains(&options.len())
+                && options.iter().all(|option| !option.is_empty() && option.len() <= 50),
+            ClawbookError::InvalidPollOptions
+        );
+        let now = Clock::get()?.unix_timestamp;
+        if let Some(closes_at) = closes_at {
+            require!(closes_at > now, ClawbookError::InvalidPollCloseTime);
+        }
+
+        let poll = &mut ctx.accounts.poll;
+        poll.post = ctx.accounts.post.key();
+        poll.creator = ctx.accounts.profile.authority;
+        poll.tallies = vec![0; options.len()];
+        poll.options = options;
+        poll.total_votes = 0;
+        poll.closes_at = closes_at.unwrap_or(0);
+        poll.created_at = now;
+
+        Ok(())
+    }
+
+    /// Vote for one option of a poll. One vote per profile, enforced by the Vote PDA.
+    pub fn vote_poll(ctx: Context<VotePoll>, option: u8) -> Result<()> {
+        let poll = &mut ctx.accounts.poll;
+        let now = Clock::get()?.unix_timestamp;
+        require!(poll.closes_at == 0 || now < poll.closes_at, ClawbookError::PollClosed);
+        require!(
+            (option as usize) < poll.options.len(),
+            ClawbookError::InvalidPollOption
+        );
+
+        let vote = &mut ctx.accounts.vote;
+        vote.voter = ctx.accounts.voter_profile.authority;
+        vote.poll = poll.key();
+        vote.option = option;
+        vote.created_at = now;
+
+        poll.tallies[option as usize] += 1;
+        poll.total_votes += 1;
+
+        Ok(())
+    }
+
+    /// Close a poll (only the creator) and reclaim its rent. Polls with a close time
+    /// can only be closed once it has passed. Polls without one run until the creator
+    /// ends voting with this instruction, which also lets voters `close_vote`. Emits
+    /// `PollClosed` with the final tallies, since the Poll account is gone afterwards.
+    pub fn close_poll(ctx: Context<ClosePoll>) -> Result<()> {
+        let poll = &ctx.accounts.poll;
+        let now = Clock::get()?.unix_timestamp;
+        require!(poll.closes_at == 0 || now >= poll.closes_at, ClawbookError::PollStillOpen);
+
+        emit!(PollClosed {
+            poll: poll.key(),
+            post: poll.post,
+            tallies: poll.tallies.clone(),
+            total_votes: poll.total_votes,
+            closed_at: now,
+        });
+
+        // Poll closed via close = authority constraint, rent returned to the creator
+        Ok(())
+    }
+
+    /// Close your Vote PDA and reclaim its rent once its poll can no longer change:
+    /// the poll's close time has passed, the poll was closed, or the poll was closed
+    /// and a new one created on the same post (the vote predates it).
+    pub fn close_vote(ctx: Context<CloseVote>) -> Result<()> {
+        let poll_ai = &ctx.accounts.poll;
+        if !poll_ai.data_is_empty() {
+            require!(*poll_ai.owner == crate::ID, ClawbookError::PollStillOpen);
+            let poll = Poll::try_deserialize(&mut &poll_ai.try_borrow_data()?[..])?;
+            let ended = poll.closes_at != 0 && Clock::get()?.unix_timestamp >= poll.closes_at;
+            let stale = ctx.accounts.vote.created_at < poll.created_at;
+            require!(ended || stale, ClawbookError::PollStillOpen);
+        }
+
+        // Vote closed via close = authority constraint, rent returned to the voter
+        Ok(())
+    }
+
+    /// Pin one of your own posts to your profile (max 3)
+    pub fn pin_post<'info>(ctx: Context<'_, '_, '_, 'info, PinPost<'info>>) -> Result<()> {
+        authorize_profile_signer(
+            &ctx.accounts.profile,
+            ctx.accounts.authority.key,
+            ctx.remaining_accounts,
+            MemberRole::Admin,
+        )?;
+        let post = ctx.accounts.post.key();
+        pin(&mut ctx.accounts.profile, post)
+    }
+
+    /// Pin one of your own compressed posts to your profile (max 3). The post is passed
+    /// back unchanged through the Light system program to prove it exists.
+    pub fn pin_compressed_post<'info>(
+        ctx: Context<'_, '_, '_, 'info, PinCompressedPost<'info>>,
+        proof: ValidityProof,
+        account_meta: CompressedAccountMeta,
+        post: CompressedPost,
+    ) -> Result<()> {
+        require!(
+            post.author == ctx.accounts.profile.authority,
+            ClawbookError::NotPostAuthor
+        );
+
+        let light_accounts = authorize_profile_signer(
+            &ctx.accounts.profile,
+            ctx.accounts.authority.key,
+            ctx.remaining_accounts,
+            MemberRole::Admin,
+        )?;
+
+        let light_cpi_accounts = CpiAccounts::new(
+            ctx.accounts.authority.as_ref(),
+            light_accounts,
+            crate::LIGHT_CPI_SIGNER,
+        );
+
+        let compressed_post = LightAccount::<CompressedPost>::new_mut(
+            &crate::ID,
+            &account_meta,
+            post,
+        )
+        .map_err(|_| error!(ClawbookError::LightCpiError))?;
+
+        LightSystemProgramCpi::new_cpi(crate::LIGHT_CPI_SIGNER, proof)
+            .with_light_account(compressed_post)
+            .map_err(|_| error!(ClawbookError::LightCpiError))?
+            .invoke(light_cpi_accounts)
+            .map_err(|_| error!(ClawbookError::LightCpiError))?;
+
+        pin(&mut ctx.accounts.profile, Pubkey::new_from_array(account_meta.address))
+    }
+
+    /// Unpin a post (regular or compressed) from your profile
+    pub fn unpin_post<'info>(
+        ctx: Context<'_, '_, '_, 'info, UnpinPost<'info>>,
+        post: Pubkey,
+    ) -> Result<()> {
+        authorize_profile_signer(
+            &ctx.accounts.profile,
+            ctx.accounts.authority.key,
+            ctx.remaining_accounts,
+            MemberRole::Admin,
+        )?;
+        let profile = &mut ctx.accounts.profile;
+        let slot = profile
+            .pinned_posts
+            .iter_mut()
+            .find(|pinned| **pinned == post && post != Pubkey::default())
+            .ok_or(error!(ClawbookError::PostNotPinned))?;
+        *slot = Pubkey::default();
+
+        Ok(())
+    }
+
+    /// Close/delete a profile (only authority can close their own profile).
+    /// The profile's Username PDA is required and released along with it, so the
+    /// name can't outlive the profile. Its ProfileMetadata, if any, is closed too.
+    pub fn close_profile(ctx: Context<CloseProfile>) -> Result<()> {
+        release_username_record(
+            ctx.accounts.authority.as_ref(),
+            ctx.accounts.username_record.as_ref(),
+            ctx.accounts.profile.authority,
+        )?;
+        let metadata = ctx.accounts.metadata.as_ref();
+        if *metadata.owner == crate::ID {
+            close_program_account(metadata, ctx.accounts.authority.as_ref())?;
+        }
+        // Remaining accounts closed via close = authority constraints, rent returned to authority
+        Ok(())
+    }
+
+    /// Release a username whose holder no longer has a profile under that name (e.g.
+    /// the profile was closed before `close_profile` released names). Permissionless;
+    /// the rent goes back to whoever paid for the record.
+    pub fn release_orphaned_username(
+        ctx: Context<ReleaseOrphanedUsername>,
+        username: String,
+    ) -> Result<()> {
+        let profile_ai = &ctx.accounts.profile;
+        if !profile_ai.data_is_empty() {
+            // Every Profile layout starts with the authority and then the username
+            let data = profile_ai.try_borrow_data()?;
+            require!(data.len() >= 8 + 32, ClawbookError::ProfileStillExists);
+            let current = String::deserialize(&mut &data[8 + 32..])
+                .map_err(|_| error!(ClawbookError::ProfileStillExists))?;
+            require!(current != username, ClawbookError::ProfileStillExists);
+        }
+
+        // Username record closed via close = payer constraint
+        Ok(())
+    }
+
+    /// Register the username of a profile created before the username registry.
+    /// Fails if someone else already holds the name, or if it doesn't meet today's
+    /// username rules (rename with `update_profile` instead).
+    pub fn claim_username(ctx: Context<ClaimUsername>) -> Result<()> {
+        validate_username(ctx.accounts.profile.username.as_bytes())
+            .map_err(|_| error!(ClawbookError::InvalidUsername))?;
+
+        let username_record = &mut ctx.accounts.username_record;
+        username_record.authority = ctx.accounts.profile.authority;
+        username_record.claimed_at = Clock::get()?.unix_timestamp;
+        username_record.payer = ctx.accounts.authority.key();
+        Ok(())
+    }
+
+    /// Record a referral — called after profile creation when user has a referral code.
+    /// Creates the referral link and increments referrer's stats.
+    pub fn record_referral(ctx: Context<RecordReferral>) -> Result<()> {
+        let referral = &mut ctx.accounts.referral;
+        referral.referred = ctx.accounts.profile.authority;
+        referral.referrer = ctx.accounts.referrer_profile.authority;
+        referral.created_at = Clock::get()?.unix_timestamp;
+
+        let referrer_stats = &mut ctx.accounts.referrer_stats;
+        referrer_stats.authority = ctx.accounts.referrer_profile.authority;
+        referrer_stats.referral_count += 1;
+
+        Ok(())
+    }
+
+    /// Update profile username, bio, and/or pfp
+    pub fn update_profile(
+        ctx: Context<UpdateProfile>,
+        username: Option<String>,
+        bio: Option<String>,
+        pfp: Option<String>,
+    ) -> Result<()> {
+        update_profile_fields(
+            &mut ctx.accounts.profile,
+            &ctx.accounts.authority,
+            ctx.accounts.old_username_record.as_ref(),
+            ctx.accounts.new_username_record.as_ref(),
+            ctx.accounts.system_program.as_ref(),
+            username,
+            bio,
+            pfp,
+        )
+    }
+
+    /// Hand a profile to a new key, e.g. after the current one leaks. The profile keeps
+    /// its address and `authority`, which stays its identity in every PDA seed (posts,
+    /// follows, likes), so nothing it references moves. From now on only
+    /// `new_authority` can sign for it; the previous key is locked out for good.
+    /// Both keys sign. An AuthorityLink PDA maps the new key back to the profile.
+    pub fn rotate_authority(ctx: Context<RotateAuthority>) -> Result<()> {
+        let profile = &mut ctx.accounts.profile;
+        let new_authority = ctx.accounts.new_authority.key();
+        require!(
+            new_authority != profile.authority && new_authority != profile.signer(),
+            ClawbookError::InvalidNewAuthority
+        );
+        // A rotated profile's current signer has a link of its own; it goes away with the key
+        require!(
+            profile.controller == Pubkey::default() || ctx.accounts.previous_link.is_some(),
+            ClawbookError::MissingAuthorityLink
+        );
+
+        let authority_link = &mut ctx.accounts.authority_link;
+        authority_link.profile = profile.key();
+        authority_link.authority = profile.authority;
+        authority_link.linked_at = Clock::get()?.unix_timestamp;
+
+        profile.controller = new_authority;
+
+        Ok(())
+    }
+
+    /// Delegate posting to an ephemeral key, e.g. one kept on an agent's server instead
+    /// of the main wallet. Until `expires_at` the session key may sign the instructions
+    /// in `permissions` (`SESSION_*` bits) for this profile, at most `daily_quota`
+    /// actions per UTC day (0 = unlimited). Rotating the profile's authority voids it.
+    pub fn create_session(
+        ctx: Context<CreateSession>,
+        session_key: Pubkey,
+        expires_at: i64,
+        permissions: u8,
+        daily_quota: u32,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(expires_at > now, ClawbookError::InvalidExpiry);
+        require!(
+            permissions != 0 && permissions & !SESSION_ALL == 0,
+            ClawbookError::InvalidSessionPermissions
+        );
+        let profile = &ctx.accounts.profile;
+        require!(
+            session_key != profile.authority && session_key != profile.signer(),
+            ClawbookError::InvalidSessionKey
+        );
+
+        let session = &mut ctx.accounts.session;
+        session.profile = profile.key();
+        session.session_key = session_key;
+        session.granted_by = profile.signer();
+        session.expires_at = expires_at;
+        session.permissions = permissions;
+        session.daily_quota = daily_quota;
+        session.day = 0;
+        session.actions_today = 0;
+        session.created_at = now;
+
+        Ok(())
+    }
+
+    /// Revoke a session key right away. Closing the SessionKey PDA is all it takes;
+    /// rent goes back to the signer.
+    pub fn revoke_session(_ctx: Context<RevokeSession>) -> Result<()> {
+        Ok(())
+    }
+
+    /// Create an organization profile run by its members. Its `authority` is the
+    /// `["organization", username]` PDA, which holds the Organization account and no
+    /// key can sign for, so the profile is only reachable through Member PDAs. The
+    /// creator becomes its owner.
+    pub fn create_organization(
+        ctx: Context<CreateOrganization>,
+        username: String,
+        bio: String,
+        pfp: String,
+    ) -> Result<()> {
+        validate_username(username.as_bytes())
+            .map_err(|e| validation_error(e, ClawbookError::UsernameTooLong))?;
+        validate_bio(bio.as_bytes()).map_err(|e| validation_error(e, ClawbookError::BioTooLong))?;
+        validate_pfp(pfp.as_bytes()).map_err(|e| validation_error(e, ClawbookError::PfpTooLong))?;
+
+        let now = Clock::get()?.unix_timestamp;
+        let profile = &mut ctx.accounts.profile;
+        profile.authority = ctx.accounts.organization.key();
+        profile.username = username;
+        profile.bio = bio;
+        profile.pfp = pfp;
+        profile.account_type = AccountType::Organization;
+        profile.bot_proof_hash = [0u8; 32];
+        profile.verified = false;
+        profile.post_count = 0;
+        profile.follower_count = 0;
+        profile.following_count = 0;
+        profile.created_at = now;
+        profile.pinned_posts = [Pubkey::default(); MAX_PINNED_POSTS];
+        profile.controller = Pubkey::default();
+
+        let username_record = &mut ctx.accounts.username_record;
+        username_record.authority = profile.authority;
+        username_record.claimed_at = now;
+        username_record.payer = ctx.accounts.authority.key();
+
+        let owner = &mut ctx.accounts.owner_member;
+        owner.profile = profile.key();
+        owner.member = ctx.accounts.authority.key();
+        owner.role = MemberRole::Owner;
+        owner.added_at = now;
+        owner.payer = ctx.accounts.authority.key();
+
+        let organization = &mut ctx.accounts.organization;
+        organization.profile = profile.key();
+        organization.member_count = 1;
+
+        Ok(())
+    }
+
+    /// Add a member to an organization. Admins and owners can add editors and admins;
+    /// ownership only moves through `transfer_org_ownership`.
+    pub fn add_org_member(ctx: Context<AddOrgMember>, role: MemberRole) -> Result<()> {
+        require!(role != MemberRole::Owner, ClawbookError::InvalidMemberRole);
+
+        let member = &mut ctx.accounts.member;
+        member.profile = ctx.accounts.profile.key();
+        member.member = ctx.accounts.new_member.key();
+        member.role = role;
+        member.added_at = Clock::get()?.unix_timestamp;
+        member.payer = ctx.accounts.authority.key();
+
+        ctx.accounts.organization.member_count += 1;
+
+        Ok(())
+    }
+
+    /// Remove an editor or admin from an organization (admins and owners only).
+    /// Rent goes back to whoever paid for the Member.
+    pub fn remove_org_member(ctx: Context<RemoveOrgMember>) -> Result<()> {
+        require!(ctx.accounts.member.role != MemberRole::Owner, ClawbookError::InvalidMemberRole);
+
+        let organization = &mut ctx.accounts.organization;
+        organization.member_count = organization.member_count.saturating_sub(1);
+
+        Ok(())
+    }
+
+    /// Hand an organization to another member. The new owner must already be a
+    /// member; the previous owner stays on as an admin.
+    pub fn transfer_org_ownership(ctx: Context<TransferOrgOwnership>) -> Result<()> {
+        ctx.accounts.new_owner_member.role = MemberRole::Owner;
+        ctx.accounts.owner_member.role = MemberRole::Admin;
+        Ok(())
+    }
+
+    /// Update an organization's profile fields (admins and owners only), as in
+    /// `update_profile`. New Username PDAs are paid by the signing member.
+    pub fn update_org_profile(
+        ctx: Context<UpdateOrgProfile>,
+        username: Option<String>,
+        bio: Option<String>,
+        pfp: Option<String>,
+    ) -> Result<()> {
+        update_profile_fields(
+            &mut ctx.accounts.profile,
+            &ctx.accounts.authority,
+            ctx.accounts.old_username_record.as_ref(),
+            ctx.accounts.new_username_record.as_ref(),
+            ctx.accounts.system_program.as_ref(),
+            username,
+            bio,
+            pfp,
+        )
+    }
+
+    /// Post as an organization. Any member may post; `signed_by` records which one.
+    pub fn create_org_post(ctx: Context<CreateOrgPost>, content: String) -> Result<()> {
+        check_content(&content)?;
+
+        let post = &mut ctx.accounts.post;
+        let profile = &mut ctx.accounts.profile;
+
+        post.author = profile.authority;
+        post.content = content;
+        post.likes = 0;
+        post.created_at = Clock::get()?.unix_timestamp;
+        post.post_id = profile.post_count;
+        post.signed_by = ctx.accounts.authority.key();
+
+        profile.post_count += 1;
+
+        Ok(())
+    }
+
+    /// Close an organization (owner only), as `close_profile` does for a person. Every
+    /// other member must be removed first. Closes the Profile, the Organization, the
+    /// owner's Member and the Username PDA; rent goes to the owner.
+    pub fn close_organization(ctx: Context<CloseOrganization>) -> Result<()> {
+        require!(
+            ctx.accounts.organization.member_count <= 1,
+            ClawbookError::OrganizationHasMembers
+        );
+
+        release_username_record(
+            ctx.accounts.authority.as_ref(),
+            ctx.accounts.username_record.as_ref(),
+            ctx.accounts.profile.authority,
+        )?;
+
+        // Profile, Organization and owner Member closed via close = authority constraints
+        Ok(())
+    }
+
+    /// Set a key/value entry on the profile's ProfileMetadata, replacing any value the
+    /// key had. The account is created on first use and resized to fit, with the
+    /// signer paying the extra rent or getting the difference back.
+    pub fn set_metadata(ctx: Context<UpdateMetadata>, key: String, value: MetadataValue) -> Result<()> {
+        validate_metadata_key(&key)?;
+        value.validate()?;
+
+        let metadata_ai = ctx.accounts.metadata.to_account_info();
+        let mut metadata = if *metadata_ai.owner == crate::ID {
+            ProfileMetadata::try_deserialize(&mut &metadata_ai.try_borrow_data()?[..])?
+        } else {
+            ProfileMetadata {
+                authority: ctx.accounts.profile.authority,
+                updated_at: 0,
+                entries: Vec::new(),
+            }
+        };
+
+        match metadata.entries.binary_search_by(|entry| entry.key.as_str().cmp(&key)) {
+            Ok(i) => metadata.entries[i].value = value,
+            Err(i) => {
+                require!(
+                    metadata.entries.len() < MAX_METADATA_ENTRIES,
+                    ClawbookError::TooManyMetadataEntries
+                );
+                metadata.entries.insert(i, MetadataEntry { key, value });
+            }
+        }
+        metadata.updated_at = Clock::get()?.unix_timestamp;
+
+        store_metadata(
+            &metadata,
+            &metadata_ai,
+            ctx.accounts.authority.as_ref(),
+            ctx.accounts.system_program.as_ref(),
+            ctx.bumps.metadata,
+        )
+    }
+
+    /// Remove a metadata entry. The account shrinks and the freed rent goes to the signer.
+    pub fn remove_metadata(ctx: Context<UpdateMetadata>, key: String) -> Result<()> {
+        let metadata_ai = ctx.accounts.metadata.to_account_info();
+        require!(*metadata_ai.owner == crate::ID, ClawbookError::MetadataKeyNotFound);
+        let mut metadata = ProfileMetadata::try_deserialize(&mut &metadata_ai.try_borrow_data()?[..])?;
+
+        let i = metadata
+            .entries
+            .binary_search_by(|entry| entry.key.as_str().cmp(&key))
+            .map_err(|_| error!(ClawbookError::MetadataKeyNotFound))?;
+        metadata.entries.remove(i);
+        if metadata.entries.is_empty() {
+            return close_program_account(&metadata_ai, ctx.accounts.authority.as_ref());
+        }
+        metadata.updated_at = Clock::get()?.unix_timestamp;
+
+        store_metadata(
+            &metadata,
+            &metadata_ai,
+            ctx.accounts.authority.as_ref(),
+            ctx.accounts.system_program.as_ref(),
+            ctx.bumps.metadata,
+        )
+    }
+
+    /// Claim a bot — links a passkey-verified human wallet to a bot profile.
+    /// One human can only claim one bot; one bot can only be claimed once.
+    pub fn claim_bot(ctx: Context<ClaimBot>) -> Result<()> {
+        let bot_profile = &ctx.accounts.bot_profile;
+        require!(bot_profile.account_type == AccountType::Bot, ClawbookError::InvalidBotProfile);
+
+        let bot_claim = &mut ctx.accounts.bot_claim;
+        bot_claim.owner = ctx.accounts.owner.key();
+        bot_claim.bot = bot_profile.authority;
+        bot_claim.claimed_at = Clock::get()?.unix_timestamp;
+
+        let human_claim = &mut ctx.accounts.human_claim;
+        human_claim.owner = ctx.accounts.owner.key();
+        human_claim.bot = bot_profile.authority;
+        human_claim.claimed_at = Clock::get()?.unix_timestamp;
+
+        Ok(())
+    }
+
+    /// Migrate an old profile to the current format (v5, 662 bytes), one
+    /// `ProfileVersion` step at a time:
+    ///   - v1 (368 bytes, no bot fields): inserts a Human account_type, empty proof hash and verified = false
+    ///   - v2 (402 bytes, no pfp field): inserts an empty pfp
+    ///   - v3 (534 bytes, no pinned posts): appends empty pin slots
+    ///   - v4 (630 bytes, no controller): appends an empty controller
+    /// Old profiles cause OOM when deserialized with the new schema because byte offsets shift.
+    /// This instruction reads raw bytes, reallocs, and rewrites the serialized fields.
+    /// The profile's signer pays the extra rent. Safe to call on already-migrated profiles
+    /// (no-op).
+    pub fn migrate_profile(ctx: Context<MigrateProfile>) -> Result<()> {
+        let profile_ai = ctx.accounts.profile.to_account_info();
+
+        // Already new format — nothing to do
+        if profile_ai.data_len() >= Profile::SPACE {
+            return Ok(());
+        }
+
+        // --- 1. Rebuild the data in the current layout ---
+        let new_data = ProfileVersion::upgrade(&profile_ai.try_borrow_data()?)?;
+        let profile = Profile::try_deserialize(&mut new_data.as_slice())?;
+        let (expected, _) =
+            Pubkey::find_program_address(&[b"profile", profile.authority.as_ref()], &crate::ID);
+        require_keys_eq!(*profile_ai.key, expected, ClawbookError::InvalidProfile);
+        require_keys_eq!(
+            profile.signer(),
+            ctx.accounts.authority.key(),
+            ClawbookError::NotProfileSigner
+        );
+
+        // --- 2. Transfer additional rent for the larger account ---
+        let rent = Rent::get()?;
+        let new_min_balance = rent.minimum_balance(Profile::SPACE);
+        let current_lamports = profile_ai.lamports();
+
+        if new_min_balance > current_lamports {
+            let diff = new_min_balance - current_lamports;
+            invoke(
+                &system_instruction::transfer(
+                    ctx.accounts.authority.key,
+                    profile_ai.key,
+                    diff,
+                ),
+                &[
+                    ctx.accounts.authority.to_account_info(),
+                    profile_ai.clone(),
+                    ctx.accounts.system_program.to_account_info(),
+                ],
+            )?;
+        }
+
+        // --- 3. Resize and rewrite data ---
+        profile_ai.resize(Profile::SPACE)?;
+        profile_ai.try_borrow_mut_data()?.copy_from_slice(&new_data);
+
+        Ok(())
+    }
+
+    /// Grow an old post (e.g. v1, 348 bytes, no thread fields) to fit the current layout.
+    /// Post fields are only ever appended and the unused tail is always zero, so the new
+    /// fields decode as their defaults once the account is large enough.
+    /// Anyone may pay for the migration (e.g. before replying). Safe to call twice (no-op).
+    pub fn migrate_post(ctx: Context<MigratePost>) -> Result<()> {
+        let post_ai = ctx.accounts.post.to_account_info();
+        let old_size = post_ai.data_len();
+
+        let space = {
+            let data = post_ai.try_borrow_data()?;
+            require!(
+                old_size >= 8 && data[..8] == *Post::DISCRIMINATOR,
+                ClawbookError::InvalidPost
+            );
+            // Fields the old layout lacks read from zero padding
+            let mut padded = data.to_vec();
+            padded.resize(old_size + Post::FIXED_SPACE, 0);
+            Post::try_deserialize(&mut padded.as_slice())?.space()
+        };
+
+        // Already new format — nothing to do
+        if old_size >= space {
+            return Ok(());
+        }
+
+        // Transfer additional rent for the larger account
+        let rent = Rent::get()?;
+        let new_min_balance = rent.minimum_balance(space);
+        let current_lamports = post_ai.lamports();
+
+        if new_min_balance > current_lamports {
+            let diff = new_min_balance - current_lamports;
+            invoke(
+                &system_instruction::transfer(ctx.accounts.payer.key, post_ai.key, diff),
+                &[
+                    ctx.accounts.payer.to_account_info(),
+                    post_ai.clone(),
+                    ctx.accounts.system_program.to_account_info(),
+                ],
+            )?;
+        }
+
+        post_ai.resize(space)?;
+
+        // Appended fields must read as zero
+        let mut data = post_ai.try_borrow_mut_data()?;
+        for byte in data[old_size..].iter_mut() {
+            *byte = 0;
+        }
+
+        Ok(())
+    }
+
+    /// Re-encode a compressed post written with an older `CompressedPost` layout.
+    /// `legacy_data` is the account data exactly as stored; the old account is burned
+    /// and re-created at the same address with the current layout.
+    pub fn migrate_compressed_post<'info>(
+        ctx: Context<'_, '_, '_, 'info, MigrateCompressedPost<'info>>,
+        proof: ValidityProof,
+        account_meta: CompressedAccountMeta,
+        legacy_data: Vec<u8>,
+    ) -> Result<()> {
+        let light_cpi_accounts = CpiAccounts::new(
+            ctx.accounts.fee_payer.as_ref(),
+            ctx.remaining_accounts,
+            crate::LIGHT_CPI_SIGNER,
+        );
+
+        let upgraded = CompressedPost::from_legacy_bytes(&legacy_data)?;
+
+        let burn_meta = CompressedAccountMetaBurn {
+            tree_info: account_meta.tree_info,
+            address: account_meta.address,
+        };
+        let legacy_post = LightAccount::<LegacyCompressedPost>::new_burn(
+            &crate::ID,
+            &burn_meta,
+            LegacyCompressedPost(legacy_data),
+        )
+        .map_err(|_| error!(ClawbookError::LightCpiError))?;
+
+        let mut compressed_post = LightAccount::<CompressedPost>::new_init(
+            &crate::ID,
+            Some(account_meta.address),
+            account_meta.output_state_tree_index,
+        );
+        *compressed_post = upgraded;
+
+        LightSystemProgramCpi::new_cpi(crate::LIGHT_CPI_SIGNER, proof)
+            .with_light_account(legacy_post)
+            .map_err(|_| error!(ClawbookError::LightCpiError))?
+            .with_light_account(compressed_post)
+            .map_err(|_| error!(ClawbookError::LightCpiError))?
+            .invoke(light_cpi_accounts)
+            .map_err(|_| error!(ClawbookError::LightCpiError))?;
+
+        Ok(())
+    }
+}
+
+// === Helpers ===
+
+/// Map a shared validation failure to its ClawbookError. `too_long` is the
+/// field's own length error.
+fn validation_error(err: ValidationError, too_long: ClawbookError) -> Error {
+    match err {
+        ValidationError::TooLong => error!(too_long),
+        ValidationError::InvalidUtf8 => error!(ClawbookError::InvalidUtf8),
+        ValidationError::ControlCharacter => error!(ClawbookError::ControlCharacter),
+        ValidationError::BidiControl => error!(ClawbookError::BidiControlCharacter),
+        ValidationError::InvalidUsername => error!(ClawbookError::InvalidUsername),
+    }
+}
+
+/// Metadata text and URL values: at most MAX_METADATA_VALUE_LEN bytes, with the same
+/// character rules as bios.
+fn validate_metadata_text(value: &str) -> Result<()> {
+    validate_text(value.as_bytes(), MAX_METADATA_VALUE_LEN, MAX_METADATA_VALUE_LEN)
+        .map_err(|e| validation_error(e, ClawbookError::InvalidMetadataValue))?;
+    Ok(())
+}
+
+/// Post content: at most 280 characters (Unicode scalar values).
+fn check_content(content: &str) -> Result<()> {
+    validate_content(content.as_bytes())
+        .map_err(|e| validation_error(e, ClawbookError::ContentTooLong))?;
+    Ok(())
+}
+
+/// Fold the content being replaced into a post's edit history:
+/// `sha256(history_hash || sha256(old_content))`.
+fn fold_content_history(history_hash: &[u8; 32], old_content: &str) -> Result<[u8; 32]> {
+    let old_content_hash =
+        Sha256::hash(old_content.as_bytes()).map_err(|_| error!(ClawbookError::HashError))?;
+    Sha256::hashv(&[history_hash, &old_content_hash]).map_err(|_| error!(ClawbookError::HashError))
+}
+
+/// Put `post` in the profile's first empty pin slot.
+fn pin(profile: &mut Profile, post: Pubkey) -> Result<()> {
+    require!(!profile.pinned_posts.contains(&post), ClawbookError::PostAlreadyPinned);
+    let slot = profile
+        .pinned_posts
+        .iter_mut()
+        .find(|pinned| **pinned == Pubkey::default())
+        .ok_or(error!(ClawbookError::TooManyPinnedPosts))?;
+    *slot = post;
+    Ok(())
+}
+
+/// Apply `update_profile` / `update_org_profile` changes. A new username moves the
+/// Username PDA: the new one is claimed (paid by `authority`) and the old one closed.
+#[allow(clippy::too_many_arguments)]
+fn update_profile_fields<'info>(
+    profile: &mut Account<'info, Profile>,
+    authority: &Signer<'info>,
+    old_username_record: Option<&UncheckedAccount<'info>>,
+    new_username_record: Option<&UncheckedAccount<'info>>,
+    system_program: Option<&Program<'info, System>>,
+    username: Option<String>,
+    bio: Option<String>,
+    pfp: Option<String>,
+) -> Result<()> {
+    if let Some(new_username) = username {
+        validate_username(new_username.as_bytes())
+            .map_err(|e| validation_error(e, ClawbookError::UsernameTooLong))?;
+        if new_username != profile.username {
+            let (Some(old_record), Some(new_record), Some(system_program)) =
+                (old_username_record, new_username_record, system_program)
+            else {
+                return err!(ClawbookError::MissingUsernameRecord);
+            };
+            let authority_ai = authority.to_account_info();
+            claim_username_record(
+                &authority_ai,
+                &new_record.to_account_info(),
+                &system_program.to_account_info(),
+                profile.authority,
+                &new_username,
+            )?;
+            release_username_record(
+                &authority_ai,
+                &old_record.to_account_info(),
+                profile.authority,
+            )?;
+        }
+        profile.username = new_username;
+    }
+
+    if let Some(new_bio) = bio {
+        validate_bio(new_bio.as_bytes())
+            .map_err(|e| validation_error(e, ClawbookError::BioTooLong))?;
+        profile.bio = new_bio;
+    }
+
+    if let Some(new_pfp) = pfp {
+        validate_pfp(new_pfp.as_bytes())
+            .map_err(|e| validation_error(e, ClawbookError::PfpTooLong))?;
+        profile.pfp = new_pfp;
+    }
+
+    Ok(())
+}
+
+/// Metadata keys are 1-32 characters of `[a-z0-9_]`, e.g. `x_handle`.
+fn validate_metadata_key(key: &str) -> Result<()> {
+    require!(
+        !key.is_empty()
+            && key.len() <= MAX_METADATA_KEY_LEN
+            && key.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'_'),
+        ClawbookError::InvalidMetadataKey
+    );
+    Ok(())
+}
+
+/// Write `metadata` to its PDA, creating it or resizing it to `metadata.space()`.
+/// `payer` covers extra rent on growth and receives the surplus on shrink.
+fn store_metadata<'info>(
+    metadata: &ProfileMetadata,
+    metadata_ai: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    bump: u8,
+) -> Result<()> {
+    let space = metadata.space();
+    if *metadata_ai.owner != crate::ID {
+        create_pda_account(
+            payer,
+            metadata_ai,
+            system_program,
+            space,
+            &[b"profile_metadata", metadata.authority.as_ref(), &[bump]],
+        )?;
+    } else {
+        let min_balance = Rent::get()?.minimum_balance(space);
+        let lamports = metadata_ai.lamports();
+        if min_balance > lamports {
+            invoke(
+                &system_instruction::transfer(payer.key, metadata_ai.key, min_balance - lamports),
+                &[payer.clone(), metadata_ai.clone(), system_program.clone()],
+            )?;
+        } else if lamports > min_balance {
+            metadata_ai.sub_lamports(lamports - min_balance)?;
+            payer.add_lamports(lamports - min_balance)?;
+        }
+        metadata_ai.resize(space)?;
+    }
+    metadata.try_serialize(&mut &mut metadata_ai.try_borrow_mut_data()?[..])?;
+    Ok(())
+}
+
+/// For a compressed post made by `compress_post`, check that `user` hasn't already
+/// liked the original post: its Like PDA must be first in `remaining_accounts` and
+/// must not hold a Like (or a Like reaction), since those are already in `likes`.
+/// Returns the remaining accounts after it.
+fn check_legacy_like<'a, 'info>(
+    post: &CompressedPost,
+    user: &Pubkey,
+    remaining_accounts: &'a [AccountInfo<'info>],
+) -> Result<&'a [AccountInfo<'info>]> {
+    if post.compressed_from == Pubkey::default() {
+        return Ok(remaining_accounts);
+    }
+
+    let (like_info, rest) = remaining_accounts
+        .split_first()
+        .ok_or(error!(ClawbookError::InvalidLikeRecord))?;
+    let (expected, _) = Pubkey::find_program_address(
+        &[b"like", user.as_ref(), post.compressed_from.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(*like_info.key, expected, ClawbookError::InvalidLikeRecord);
+
+    if *like_info.owner == crate::ID {
+        let data = like_info.try_borrow_data()?;
+        let liked = if data.starts_with(Reaction::DISCRIMINATOR) {
+            Reaction::try_deserialize(&mut &data[..])?.kind == ReactionKind::Like
+        } else {
+            data.starts_with(Like::DISCRIMINATOR)
+        };
+        require!(!liked, ClawbookError::AlreadyLiked);
+    }
+    Ok(rest)
+}
+
+/// Check that `signer` may act for `profile`: either it is the profile's signer (or an
+/// organization member, see `authorize_profile_signer`), or a SessionKey PDA for it is
+/// first in `remaining_accounts`, unexpired, granted by the current signer and allows
+/// `permission`. A session's daily count goes up by `actions`. Returns the remaining
+/// accounts after the session or Member, if any.
+fn authorize_signer<'a, 'info>(
+    profile: &Account<'info, Profile>,
+    signer: &Pubkey,
+    remaining_accounts: &'a [AccountInfo<'info>],
+    permission: u8,
+    actions: u32,
+) -> Result<&'a [AccountInfo<'info>]> {
+    if profile.signer() == *signer || profile.account_type == AccountType::Organization {
+        return authorize_profile_signer(profile, signer, remaining_accounts, MemberRole::Editor);
+    }
+
+    let (session_info, rest) = remaining_accounts
+        .split_first()
+        .ok_or(error!(ClawbookError::NotProfileSigner))?;
+    let (expected, _) = Pubkey::find_program_address(
+        &[b"session", profile.key().as_ref(), signer.as_ref()],
+        &crate::ID,
+    );
+    require!(
+        *session_info.key == expected && *session_info.owner == crate::ID && session_info.is_writable,
+        ClawbookError::NotProfileSigner
+    );
+    let mut session = SessionKey::try_deserialize(&mut &session_info.try_borrow_data()?[..])?;
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        now < session.expires_at && session.granted_by == profile.signer(),
+        ClawbookError::SessionExpired
+    );
+    require!(session.permissions & permission != 0, ClawbookError::SessionNotPermitted);
+
+    let day = now / 86_400;
+    if session.day != day {
+        session.day = day;
+        session.actions_today = 0;
+    }
+    session.actions_today = session.actions_today.saturating_add(actions);
+    require!(
+        session.daily_quota == 0 || session.actions_today <= session.daily_quota,
+        ClawbookError::SessionQuotaExceeded
+    );
+    session.try_serialize(&mut &mut session_info.try_borrow_mut_data()?[..])?;
+
+    Ok(rest)
+}
+
+/// Check that `signer` can act for `profile`: its current signer, or a member of an
+/// organization profile with at least `role`. Members pass their Member PDA first in
+/// `remaining_accounts`. Returns the accounts after it.
+fn authorize_profile_signer<'a, 'info>(
+    profile: &Account<'info, Profile>,
+    signer: &Pubkey,
+    remaining_accounts: &'a [AccountInfo<'info>],
+    role: MemberRole,
+) -> Result<&'a [AccountInfo<'info>]> {
+    if profile.signer() == *signer {
+        return Ok(remaining_accounts);
+    }
+    require!(
+        profile.account_type == AccountType::Organization,
+        ClawbookError::NotProfileSigner
+    );
+
+    let (member_info, rest) = remaining_accounts
+        .split_first()
+        .ok_or(error!(ClawbookError::NotProfileSigner))?;
+    let (expected, _) = Pubkey::find_program_address(
+        &[b"member", profile.key().as_ref(), signer.as_ref()],
+        &crate::ID,
+    );
+    require!(
+        *member_info.key == expected && *member_info.owner == crate::ID,
+        ClawbookError::NotProfileSigner
+    );
+    let member = Member::try_deserialize(&mut &member_info.try_borrow_data()?[..])?;
+    require!(member.role >= role, ClawbookError::NotOrgAdmin);
+
+    Ok(rest)
+}
+
+/// Role an organization member needs to edit a post: editors may only edit the posts
+/// they signed (`signed_by`), admins any of them.
+fn edit_role(signed_by: Pubkey, signer: &Pubkey) -> MemberRole {
+    if signed_by == *signer {
+        MemberRole::Editor
+    } else {
+        MemberRole::Admin
+    }
+}
+
+/// Shared body of `create_post` and `create_post_with_options`.
+fn publish_post<'info>(
+    post: &mut Account<'info, Post>,
+    profile: &mut Account<'info, Profile>,
+    authority: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    remaining_accounts: &[AccountInfo<'info>],
+    content: String,
+    options: PostOptions,
+) -> Result<()> {
+    check_content(&content)?;
+    let now = Clock::get()?.unix_timestamp;
+    match options.expires_at {
+        Some(expires_at) => require!(expires_at > now, ClawbookError::InvalidExpiry),
+        None => require!(options.crank_reward == 0, ClawbookError::InvalidExpiry),
+    }
+    require!(
+        options.crank_reward <= MAX_CRANK_REWARD_LAMPORTS,
+        ClawbookError::CrankRewardTooHigh
+    );
+    validate_attachments(&options.attachments)?;
+    let tags = normalize_tags(options.tags)?;
+    let mentions = options.mentions;
+    let crank_reward = options.crank_reward;
+    let remaining = authorize_signer(profile, authority.key, remaining_accounts, SESSION_POST, 1)?;
+    let topic_accounts = verify_mentions(&mentions, remaining)?;
+
+    let author = profile.authority;
+    let post_key = post.key();
+    let created_at = now;
+    let light_accounts = record_topics(
+        &tags,
+        topic_accounts,
+        authority.as_ref(),
+        system_program.as_ref(),
+        created_at,
+    )?;
+
+    post.author = author;
+    post.content = content;
+    post.likes = 0;
+    post.created_at = created_at;
+    post.post_id = profile.post_count;
+    post.attachments = options.attachments;
+    post.tags = tags;
+    post.expires_at = options.expires_at.unwrap_or(0);
+    post.crank_reward = crank_reward;
+    post.signed_by = profile.post_signer(authority.key);
+
+    profile.post_count += 1;
+
+    if crank_reward > 0 {
+        invoke(
+            &system_instruction::transfer(authority.key, &post_key, crank_reward),
+            &[
+                authority.to_account_info(),
+                post.to_account_info(),
+                system_program.to_account_info(),
+            ],
+        )?;
+    }
+
+    if !mentions.is_empty() {
+        let light_cpi_accounts =
+            CpiAccounts::new(authority.as_ref(), light_accounts, crate::LIGHT_CPI_SIGNER);
+        let mut cpi =
+            LightSystemProgramCpi::new_cpi(crate::LIGHT_CPI_SIGNER, options.mention_proof);
+        for mention in new_mentions(
+            &mentions,
+            post_key,
+            author,
+            created_at,
+            options.mention_tree_index,
+        ) {
+            cpi = cpi
+                .with_light_account(mention)
+                .map_err(|_| error!(ClawbookError::LightCpiError))?;
+        }
+        cpi.invoke(light_cpi_accounts)
+            .map_err(|_| error!(ClawbookError::LightCpiError))?;
+    }
+
+    Ok(())
+}
+
+/// Check each mentioned authority against its Profile PDA, passed in the same order at
+/// the start of `remaining_accounts`. Returns the accounts after them.
+fn verify_mentions<'a, 'info>(
+    mentions: &[Pubkey],
+    remaining_accounts: &'a [AccountInfo<'info>],
+) -> Result<&'a [AccountInfo<'info>]> {
+    require!(mentions.len() <= MAX_MENTIONS, ClawbookError::TooManyMentions);
+    require!(remaining_accounts.len() >= mentions.len(), ClawbookError::InvalidMention);
+
+    let (profiles, rest) = remaining_accounts.split_at(mentions.len());
+    for (i, (mention, profile)) in mentions.iter().zip(profiles).enumerate() {
+        require!(!mentions[..i].contains(mention), ClawbookError::InvalidMention);
+        let (expected, _) =
+            Pubkey::find_program_address(&[b"profile", mention.as_ref()], &crate::ID);
+        let data = profile.try_borrow_data()?;
+        require!(
+            *profile.key == expected
+                && *profile.owner == crate::ID
+                && data.len() >= 8
+                && data[..8] == *Profile::DISCRIMINATOR,
+            ClawbookError::InvalidMention
+        );
+    }
+    Ok(rest)
+}
+
+/// Lowercase each hashtag (dropping an optional leading `#`) and check it is 1-32
+/// characters of `[a-z0-9_]`, so it can be used as a Topic seed. Rejects duplicates.
+fn normalize_tags(tags: Vec<String>) -> Result<Vec<String>> {
+    require!(tags.len() <= MAX_TAGS, ClawbookError::TooManyTags);
+
+    let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+    for tag in tags {
+        let tag = tag.strip_prefix('#').unwrap_or(&tag).to_ascii_lowercase();
+        require!(
+            !tag.is_empty()
+                && tag.len() <= 32
+                && tag.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'_')
+                && !normalized.contains(&tag),
+            ClawbookError::InvalidTag
+        );
+        normalized.push(tag);
+    }
+    Ok(normalized)
+}
+
+/// Count a new post on each tag's Topic PDA, passed in the same order at the start of
+/// `accounts`. A Topic that doesn't exist yet is created, paid by `payer`.
+/// Returns the accounts after them.
+fn record_topics<'a, 'info>(
+    tags: &[String],
+    accounts: &'a [AccountInfo<'info>],
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    now: i64,
+) -> Result<&'a [AccountInfo<'info>]> {
+    require!(accounts.len() >= tags.len(), ClawbookError::InvalidTopic);
+
+    let (topics, rest) = accounts.split_at(tags.len());
+    for (tag, topic_ai) in tags.iter().zip(topics) {
+        let (expected, bump) =
+            Pubkey::find_program_address(&[b"topic", tag.as_bytes()], &crate::ID);
+        require!(*topic_ai.key == expected, ClawbookError::InvalidTopic);
+
+        let mut topic = if *topic_ai.owner == crate::ID {
+            Topic::try_deserialize(&mut &topic_ai.try_borrow_data()?[..])?
+        } else {
+            create_pda_account(
+                payer,
+                topic_ai,
+                system_program,
+                Topic::SPACE,
+                &[b"topic", tag.as_bytes(), &[bump]],
+            )?;
+            Topic {
+                tag: tag.clone(),
+                post_count: 0,
+                follower_count: 0,
+                last_post_at: 0,
+                created_at: now,
+            }
+        };
+
+        topic.post_count += 1;
+        topic.last_post_at = now;
+        topic.try_serialize(&mut &mut topic_ai.try_borrow_mut_data()?[..])?;
+    }
+    Ok(rest)
+}
+
+/// Create a program-owned PDA of `space` bytes, rent paid by `payer`. Also works when
+/// the address already holds lamports (where `create_account` would fail).
+fn create_pda_account<'info>(
+    payer: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+    signer_seeds: &[&[u8]],
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(space);
+    let lamports = account.lamports();
+
+    if lamports == 0 {
+        invoke_signed(
+            &system_instruction::create_account(payer.key, account.key, rent, space as u64, &crate::ID),
+            &[payer.clone(), account.clone(), system_program.clone()],
+            &[signer_seeds],
+        )?;
+        return Ok(());
+    }
+
+    if rent > lamports {
+        invoke(
+            &system_instruction::transfer(payer.key, account.key, rent - lamports),
+            &[payer.clone(), account.clone(), system_program.clone()],
+        )?;
+    }
+    invoke_signed(
+        &system_instruction::allocate(account.key, space as u64),
+        &[account.clone(), system_program.clone()],
+        &[signer_seeds],
+    )?;
+    invoke_signed(
+        &system_instruction::assign(account.key, &crate::ID),
+        &[account.clone(), system_program.clone()],
+        &[signer_seeds],
+    )?;
+    Ok(())
+}
+
+/// Create the Username PDA for `username`, pointing at the profile's original
+/// `authority` and paid by `payer`. Fails if the name is already held.
+fn claim_username_record<'info>(
+    payer: &AccountInfo<'info>,
+    record: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    authority: Pubkey,
+    username: &str,
+) -> Result<()> {
+    let (expected, bump) =
+        Pubkey::find_program_address(&[b"username", username.as_bytes()], &crate::ID);
+    require_keys_eq!(*record.key, expected, ClawbookError::MissingUsernameRecord);
+    require!(record.data_is_empty(), ClawbookError::UsernameTaken);
+
+    create_pda_account(
+        payer,
+        record,
+        system_program,
+        Username::SPACE,
+        &[b"username", username.as_bytes(), &[bump]],
+    )?;
+    let entry = Username {
+        authority,
+        claimed_at: Clock::get()?.unix_timestamp,
+        payer: *payer.key,
+    };
+    entry.try_serialize(&mut &mut record.try_borrow_mut_data()?[..])?;
+    Ok(())
+}
+
+/// Close the Username PDA if `authority` holds it, returning the rent to `recipient`.
+/// Names of profiles created before the registry may be unclaimed or held by
+/// someone else; those are left alone.
+fn release_username_record<'info>(
+    recipient: &AccountInfo<'info>,
+    record: &AccountInfo<'info>,
+    authority: Pubkey,
+) -> Result<()> {
+    if *record.owner != crate::ID || record.data_is_empty() {
+        return Ok(());
+    }
+    let entry = Username::try_deserialize(&mut &record.try_borrow_data()?[..])?;
+    if entry.authority == authority {
+        close_program_account(record, recipient)?;
+    }
+    Ok(())
+}
+
+/// Close an account this program owns, moving all its lamports to `recipient`.
+fn close_program_account<'info>(
+    account: &AccountInfo<'info>,
+    recipient: &AccountInfo<'info>,
+) -> Result<()> {
+    let lamports = account.lamports();
+    account.sub_lamports(lamports)?;
+    recipient.add_lamports(lamports)?;
+    account.assign(&system_program::ID);
+    account.resize(0)?;
+    Ok(())
+}
+
+/// One CompressedMention per recipient, without addresses. Recipients find them by
+/// filtering the program's compressed accounts on the leading `recipient` field.
+fn new_mentions(
+    mentions: &[Pubkey],
+    post: Pubkey,
+    author: Pubkey,
+    created_at: i64,
+    output_tree_index: u8,
+) -> Vec<LightAccount<CompressedMention>> {
+    mentions
+        .iter()
+        .map(|recipient| {
+            let mut mention =
+                LightAccount::<CompressedMention>::new_init(&crate::ID, None, output_tree_index);
+            mention.recipient = *recipient;
+            mention.post = post;
+            mention.author = author;
+            mention.created_at = created_at;
+            mention
+        })
+        .collect()
+}
+
+/// Check a post's attachment list against the count limit and each attachment's scheme,
+/// identifier, MIME type, size and hash.
+fn validate_attachments(attachments: &[MediaAttachment]) -> Result<()> {
+    require!(attachments.len() <= MAX_ATTACHMENTS, ClawbookError::TooManyAttachments);
+    for attachment in attachments {
+        require!(
+            attachment.id.len() <= 128 && attachment.scheme.is_valid_id(&attachment.id),
+            ClawbookError::InvalidAttachmentId
+        );
+        require!(
+            attachment.mime_type.len() <= 32 && is_valid_mime_type(&attachment.mime_type),
+            ClawbookError::InvalidMimeType
+        );
+        require!(attachment.size > 0, ClawbookError::InvalidAttachmentSize);
+        require!(attachment.sha256 != [0u8; 32], ClawbookError::InvalidAttachmentHash);
+    }
+    Ok(())
+}
+
+/// `type/subtype`, lowercase, each part made of RFC 6838 restricted-name characters.
+fn is_valid_mime_type(mime_type: &str) -> bool {
+    let is_part = |part: &str| {
+        !part.is_empty()
+            && part.bytes().all(|b| {
+                b.is_ascii_lowercase() || b.is_ascii_digit() || b"!#$&-^_.+".contains(&b)
+            })
+    };
+    match mime_type.split_once('/') {
+        Some((kind, subtype)) => is_part(kind) && is_part(subtype),
+        None => false,
+    }
+}
+
+// === Account Type Enum ===
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AccountType {
+    #[default]
+    Human = 0,
+    Bot = 1,
+    Organization = 2,
+}
+
+/// A member's role in an organization. Ordered: each role can do everything the
+/// ones before it can.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum MemberRole {
+    /// Posts as the organization and edits the posts it signed
+    #[default]
+    Editor,
+    /// Also edits, deletes, compresses and pins any of its posts, adds and removes
+    /// members and edits the profile
+    Admin,
+    /// Also transfers ownership. One per organization.
+    Owner,
+}
+
+/// A typed ProfileMetadata value. Borsh-encoded as a 1-byte variant index followed by
+/// the payload; variants are only ever appended, so indexers can rely on the tags.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum MetadataValue {
+    /// Free text, up to MAX_METADATA_VALUE_LEN bytes
+    Text(String),
+    /// `https://` URL, up to MAX_METADATA_VALUE_LEN bytes
+    Url(String),
+    Pubkey(Pubkey),
+    Integer(i64),
+    Bool(bool),
+}
+
+impl MetadataValue {
+    /// Encoded size in bytes, including the variant tag.
+    pub fn space(&self) -> usize {
+        1 + match self {
+            MetadataValue::Text(text) | MetadataValue::Url(text) => 4 + text.len(),
+            MetadataValue::Pubkey(_) => 32,
+            MetadataValue::Integer(_) => 8,
+            MetadataValue::Bool(_) => 1,
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            MetadataValue::Text(text) => {
+                validate_metadata_text(text)?;
+            }
+            MetadataValue::Url(url) => {
+                validate_metadata_text(url)?;
+                require!(url.starts_with("https://"), ClawbookError::InvalidMetadataValue);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+/// Who can join a community.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum JoinPolicy {
+    /// Anyone with a profile joins immediately
+    #[default]
+    Open = 0,
+    /// Memberships wait for a moderator's `approve_member`
+    Approval = 1,
+}
+
+/// Kind of reaction to a post. `Like` is the same reaction `like_post` records.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ReactionKind {
+    #[default]
+    Like = 0,
+    Agree = 1,
+    Disagree = 2,
+    Insightful = 3,
+    Funny = 4,
+    Hallucination = 5,
+}
+
+/// Where an attachment's bytes are stored. Decides how `MediaAttachment::id` is read.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum MediaScheme {
+    /// `id` is a 43-character base64url Arweave transaction ID
+    #[default]
+    Arweave = 0,
+    /// `id` is a CIDv0 (`Qm...`) or base32 CIDv1 (`b...`)
+    Ipfs = 1,
+    /// `id` is a full `https://` URL
+    Https = 2,
+}
+
+impl MediaScheme {
+    pub fn is_valid_id(&self, id: &str) -> bool {
+        match self {
+            MediaScheme::Arweave => {
+                id.len() == 43
+                    && id.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+            }
+            MediaScheme::Ipfs => {
+                const BASE58: &[u8] =
+                    b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+                if let Some(rest) = id.strip_prefix('b') {
+                    !rest.is_empty()
+                        && rest.bytes().all(|b| b.is_ascii_lowercase() || (b'2'..=b'7').contains(&b))
+                } else {
+                    id.len() == 46 && id.starts_with("Qm") && id.bytes().all(|b| BASE58.contains(&b))
+                }
+            }
+            MediaScheme::Https => match id.strip_prefix("https://") {
+                Some(rest) => {
+                    let host = rest.split(['/', '?', '#']).next().unwrap_or_default();
+                    !host.is_empty() && id.bytes().all(|b| b.is_ascii_graphic())
+                }
+                None => false,
+            },
+        }
+    }
+}
+
+// === Account Structures ===
+
+#[account]
+pub struct Profile {
+    pub authority: Pubkey,          // 32 bytes
+    pub username: String,           // 4 + 32 bytes
+    pub bio: String,                // 4 + 256 bytes
+    pub pfp: String,                // 4 + 128 bytes (URL/IPFS/Arweave)
+    pub account_type: AccountType,  // 1 byte
+    pub bot_proof_hash: [u8; 32],   // 32 bytes (SHA256 of proof)
+    pub verified: bool,             // 1 byte
+    pub post_count: u64,            // 8 bytes
+    pub follower_count: u64,        // 8 bytes
+    pub following_count: u64,       // 8 bytes
+    pub created_at: i64,            // 8 bytes
+    pub pinned_posts: [Pubkey; 3],  // 96 bytes — Post PDAs or compressed post addresses (default = empty slot)
+    pub controller: Pubkey,         // 32 bytes — key set by rotate_authority (default = not rotated)
+}
+
+impl Profile {
+    /// v1 (368 bytes) had no bot fields, v2 (402 bytes) had no pfp, v3 (534 bytes) had
+    /// no pinned posts, v4 (630 bytes) had no controller. See `ProfileVersion`.
+    pub const SPACE: usize = 8 + 32 + (4 + 32) + (4 + 256) + (4 + 128) + 1 + 32 + 1 + 8 + 8 + 8 + 8
+        + 32 * MAX_PINNED_POSTS
+        + 32; // 662 bytes
+
+    /// Key allowed to sign for this profile: `authority` until `rotate_authority`
+    /// hands it to another key. `authority` itself never changes.
+    pub fn signer(&self) -> Pubkey {
+        if self.controller == Pubkey::default() {
+            self.authority
+        } else {
+            self.controller
+        }
+    }
+
+    /// `Post::signed_by` for a post `signer` writes as this profile: the member for
+    /// organizations, default (the author) otherwise.
+    pub fn post_signer(&self, signer: &Pubkey) -> Pubkey {
+        if self.account_type == AccountType::Organization {
+            *signer
+        } else {
+            Pubkey::default()
+        }
+    }
+}
+
+/// Historical `Profile` layouts, told apart by account size. Each version adds fields
+/// to the previous one's serialized data:
+///   v1 (368): authority, username, bio, post_count, follower_count, following_count, created_at
+///   v2 (402): + account_type, bot_proof_hash, verified (after bio)
+///   v3 (534): + pfp (after bio)
+///   v4 (630): + pinned_posts (at the end)
+///   v5 (662): + controller (at the end)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ProfileVersion {
+    V1,
+    V2,
+    V3,
+    V4,
+    V5,
+}
+
+impl ProfileVersion {
+    pub const CURRENT: ProfileVersion = ProfileVersion::V5;
+
+    /// Account size of this layout.
+    pub fn size(self) -> usize {
+        match self {
+            ProfileVersion::V1 => 368,
+            ProfileVersion::V2 => 402,
+            ProfileVersion::V3 => 534,
+            ProfileVersion::V4 => 630,
+            ProfileVersion::V5 => Profile::SPACE,
+        }
+    }
+
+    pub fn from_size(size: usize) -> Option<ProfileVersion> {
+        [
+            ProfileVersion::V1,
+            ProfileVersion::V2,
+            ProfileVersion::V3,
+            ProfileVersion::V4,
+            ProfileVersion::V5,
+        ]
+        .into_iter()
+        .find(|version| version.size() == size)
+    }
+
+    fn next(self) -> Option<ProfileVersion> {
+        match self {
+            ProfileVersion::V1 => Some(ProfileVersion::V2),
+            ProfileVersion::V2 => Some(ProfileVersion::V3),
+            ProfileVersion::V3 => Some(ProfileVersion::V4),
+            ProfileVersion::V4 => Some(ProfileVersion::V5),
+            ProfileVersion::V5 => None,
+        }
+    }
+
+    /// Leading borsh strings: username and bio, plus pfp from v3 on.
+    fn string_count(self) -> usize {
+        if self >= ProfileVersion::V3 { 3 } else { 2 }
+    }
+
+    /// Bytes of fixed-size fields after the strings.
+    fn tail_len(self) -> usize {
+        const BOT_FIELDS: usize = 1 + 32 + 1; // account_type + bot_proof_hash + verified
+        const COUNTERS: usize = 8 + 8 + 8 + 8; // post/follower/following counts + created_at
+        match self {
+            ProfileVersion::V1 => COUNTERS,
+            ProfileVersion::V2 | ProfileVersion::V3 => BOT_FIELDS + COUNTERS,
+            ProfileVersion::V4 => BOT_FIELDS + COUNTERS + 32 * MAX_PINNED_POSTS,
+            ProfileVersion::V5 => BOT_FIELDS + COUNTERS + 32 * MAX_PINNED_POSTS + 32,
+        }
+    }
+
+    /// End of the strings in `data` (discriminator and authority first).
+    fn strings_end(self, data: &[u8]) -> Result<usize> {
+        let mut cursor = 8 + 32;
+        for _ in 0..self.string_count() {
+            let len = data
+                .get(cursor..cursor + 4)
+                .ok_or(error!(ClawbookError::InvalidProfile))?;
+            cursor += 4 + u32::from_le_bytes(len.try_into().unwrap()) as usize;
+        }
+        Ok(cursor)
+    }
+
+    /// Rewrite `data` (this layout) as the next layout, zero-padded to its size.
+    /// Bytes past the serialized fields, e.g. left over from `update_profile`
+    /// shortening a string, are dropped.
+    fn upgrade_step(self, data: &[u8]) -> Result<(ProfileVersion, Vec<u8>)> {
+        let next = self.next().ok_or(error!(ClawbookError::InvalidProfile))?;
+        let strings_end = self.strings_end(data)?;
+        let fields_end = strings_end + self.tail_len();
+        require!(fields_end <= data.len(), ClawbookError::InvalidProfile);
+
+        let (at, inserted): (usize, &[u8]) = match self {
+            ProfileVersion::V1 => (strings_end, &[0u8; 1 + 32 + 1]), // Human, no proof, unverified
+            ProfileVersion::V2 => (strings_end, &[0u8; 4]),          // empty pfp
+            ProfileVersion::V3 => (fields_end, &[0u8; 32 * MAX_PINNED_POSTS]), // empty pin slots
+            ProfileVersion::V4 => (fields_end, &[0u8; 32]),          // no controller
+            ProfileVersion::V5 => unreachable!(),
+        };
+
+        let mut new_data = Vec::with_capacity(next.size());
+        new_data.extend_from_slice(&data[..at]);
+        new_data.extend_from_slice(inserted);
+        new_data.extend_from_slice(&data[at..fields_end]);
+        require!(new_data.len() <= next.size(), ClawbookError::InvalidProfile);
+        new_data.resize(next.size(), 0);
+        Ok((next, new_data))
+    }
+
+    /// Upgrade raw Profile account data from any historical layout to the current one.
+    pub fn upgrade(data: &[u8]) -> Result<Vec<u8>> {
+        let mut version =
+            ProfileVersion::from_size(data.len()).ok_or(error!(ClawbookError::InvalidProfile))?;
+        require!(
+            data[..8] == *Profile::DISCRIMINATOR,
+            ClawbookError::InvalidProfile
+        );
+
+        let mut data = data.to_vec();
+        while version < ProfileVersion::CURRENT {
+            (version, data) = version.upgrade_step(&data)?;
+        }
+        Ok(data)
+    }
+}
+
+/// Maps a key that took over a profile through `rotate_authority` back to it, at
+/// `["authority_link", key]`. One profile per key.
+#[account]
+pub struct AuthorityLink {
+    pub profile: Pubkey,            // 32 bytes — Profile PDA
+    pub authority: Pubkey,          // 32 bytes — the profile's original authority
+    pub linked_at: i64,             // 8 bytes
+}
+
+impl AuthorityLink {
+    pub const SPACE: usize = 8 + 32 + 32 + 8; // 80 bytes
+}
+
+/// Delegated signing key for a profile at `["session", profile, session_key]`.
+/// See `create_session`.
+#[account]
+pub struct SessionKey {
+    pub profile: Pubkey,            // 32 bytes — Profile PDA
+    pub session_key: Pubkey,        // 32 bytes — ephemeral key allowed to sign
+    pub granted_by: Pubkey,         // 32 bytes — profile signer that created it
+    pub expires_at: i64,            // 8 bytes
+    pub permissions: u8,            // 1 byte — SESSION_* bits
+    pub daily_quota: u32,           // 4 bytes — max actions per UTC day, 0 = unlimited
+    pub day: i64,                   // 8 bytes — UTC day (unix / 86400) of actions_today
+    pub actions_today: u32,         // 4 bytes
+    pub created_at: i64,            // 8 bytes
+}
+
+impl SessionKey {
+    pub const SPACE: usize = 8 + 32 + 32 + 32 + 8 + 1 + 4 + 8 + 4 + 8; // 137 bytes
+}
+
+/// A key's role in an organization profile, at `["member", profile, member]`.
+#[account]
+pub struct Member {
+    pub profile: Pubkey,            // 32 bytes — organization Profile PDA
+    pub member: Pubkey,             // 32 bytes — member's wallet
+    pub role: MemberRole,           // 1 byte
+    pub added_at: i64,              // 8 bytes
+    pub payer: Pubkey,              // 32 bytes — paid the rent, gets it back on removal
+}
+
+impl Member {
+    pub const SPACE: usize = 8 + 32 + 32 + 1 + 8 + 32; // 113 bytes
+}
+
+/// An organization's member count, at its `["organization", username]` identity PDA
+/// (the profile's `authority`). The organization can only be closed once its owner is
+/// the last member, so no Member outlives it.
+#[account]
+pub struct Organization {
+    pub profile: Pubkey,            // 32 bytes — organization Profile PDA
+    pub member_count: u32,          // 4 bytes — including the owner
+}
+
+impl Organization {
+    pub const SPACE: usize = 8 + 32 + 4; // 44 bytes
+}
+
+/// Extra profile fields at `["profile_metadata", authority]`, sized to its entries.
+/// Entries are kept sorted by key, one per key.
+#[account]
+pub struct ProfileMetadata {
+    pub authority: Pubkey,          // 32 bytes — profile's original authority
+    pub updated_at: i64,            // 8 bytes
+    pub entries: Vec<MetadataEntry>, // 4 + sum of entry sizes
+}
+
+impl ProfileMetadata {
+    pub const BASE_SPACE: usize = 8 + 32 + 8 + 4; // 52 bytes
+
+    /// Account size needed for the current entries.
+    pub fn space(&self) -> usize {
+        Self::BASE_SPACE + self.entries.iter().map(MetadataEntry::space).sum::<usize>()
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MetadataEntry {
+    pub key: String,                // 4 + up to 32 bytes
+    pub value: MetadataValue,       // see MetadataValue::space
+}
+
+impl MetadataEntry {
+    pub fn space(&self) -> usize {
+        4 + self.key.len() + self.value.space()
+    }
+}
+
+/// Username registry entry at `["username", username]`. Usernames are already
+/// canonical (lowercase a-z, 0-9, _), so the seed is the username itself.
+#[account]
+pub struct Username {
+    pub authority: Pubkey,          // 32 bytes — profile authority holding the name
+    pub claimed_at: i64,            // 8 bytes
+    pub payer: Pubkey,              // 32 bytes — paid the rent, gets it back on release
+}
+
+impl Username {
+    pub const SPACE: usize = 8 + 32 + 8 + 32; // 80 bytes
+}
+
+/// Sized to its actual content, attachments and tags (see `Post::space_for`).
+/// New fields are only ever appended, and bytes past the serialized data stay zero,
+/// so `migrate_post` can upgrade old posts with a plain realloc.
+#[account]
+#[derive(Default)]
+pub struct Post {
+    pub author: Pubkey,             // 32 bytes
+    pub content: String,            // 4 + up to MAX_CONTENT_BYTES (280 characters)
+    pub likes: u64,                 // 8 bytes
+    pub created_at: i64,            // 8 bytes
+    pub post_id: u64,               // 8 bytes
+    pub parent: Pubkey,             // 32 bytes — post replied to (default = top-level post)
+    pub root: Pubkey,               // 32 bytes — first post of the thread (default = top-level post)
+    pub reply_count: u64,           // 8 bytes
+    pub repost_count: u64,          // 8 bytes
+    pub quoted_post: Pubkey,        // 32 bytes — post quoted by this one (default = not a quote)
+    pub quote_count: u64,           // 8 bytes
+    pub revision: u32,              // 4 bytes — number of edits
+    pub edited_at: i64,             // 8 bytes — last edit (0 = never edited)
+    pub history_hash: [u8; 32],     // 32 bytes — running digest of previous contents
+    pub attachments: Vec<MediaAttachment>, // 4 + up to 4 * 209 bytes
+    pub tags: Vec<String>,          // 4 + up to 3 * (4 + 32) bytes — normalized hashtags
+    pub expires_at: i64,            // 8 bytes — 0 = never expires
+    pub crank_reward: u64,          // 8 bytes — lamports deposited for whoever calls expire_post
+    pub reaction_counts: [u64; 5],  // 40 bytes — Agree, Disagree, Insightful, Funny, Hallucination
+    pub community: Pubkey,          // 32 bytes — Community PDA (default = not a community post)
+    pub hidden: bool,               // 1 byte — hidden by a community moderator
+    pub boost_score: u64,           // 8 bytes — boost lamports as of the last boost
+    pub boost_expires_at: i64,      // 8 bytes — last boost + BOOST_DURATION_SECS (0 = never boosted)
+    pub signed_by: Pubkey,          // 32 bytes — organization member who signed (default = the author)
+}
+
+impl Post {
+    /// Every field but `content`, `attachments` and `tags`, whose actual serialized
+    /// size `space_for` adds on top. The original layout was a fixed 348 bytes
+    /// (author, 280 bytes of content, likes, created_at, post_id); `migrate_post`
+    /// grows such posts to this one.
+    pub const FIXED_SPACE: usize = 8 + 32 + 4 + 8 + 8 + 8 + 32 + 32 + 8 + 8 + 32 + 8 + 4 + 8 + 32
+        + 4
+        + 4
+        + 8 + 8
+        + 8 * 5
+        + 32 + 1
+        + 8 + 8
+        + 32; // 377 bytes
+
+    /// Account size for a post with this content, attachments and tags.
+    pub fn space_for(content: &str, attachments: &[MediaAttachment], tags: &[String]) -> usize {
+        Self::FIXED_SPACE
+            + content.len()
+            + attachments.iter().map(MediaAttachment::space).sum::<usize>()
+            + tags.iter().map(|tag| 4 + tag.len()).sum::<usize>()
+    }
+
+    /// Account size this post needs.
+    pub fn space(&self) -> usize {
+        Self::space_for(&self.content, &self.attachments, &self.tags)
+    }
+
+    /// Who gets the rent back when someone other than the author closes the post: the
+    /// member who signed an organization post (the organization itself can't spend
+    /// it), otherwise the author profile's current signer.
+    pub fn rent_receiver(&self, author_profile: &Profile) -> Pubkey {
+        if self.signed_by != Pubkey::default() {
+            self.signed_by
+        } else {
+            author_profile.signer()
+        }
+    }
+
+    /// Counter for a reaction kind. Likes keep using `likes`.
+    pub fn reaction_count_mut(&mut self, kind: ReactionKind) -> &mut u64 {
+        match kind {
+            ReactionKind::Like => &mut self.likes,
+            _ => &mut self.reaction_counts[kind as usize - 1],
+        }
+    }
+
+    /// Boost in effect at `now`: `boost_score` falling linearly to zero between the
+    /// last boost (`boost_expires_at - BOOST_DURATION_SECS`) and `boost_expires_at`,
+    /// rounded down. Feeds can reproduce it exactly with the same integer math.
+    pub fn effective_boost(&self, now: i64) -> u64 {
+        let remaining = self.boost_expires_at.saturating_sub(now);
+        if remaining <= 0 {
+            return 0;
+        }
+        let remaining = remaining.min(BOOST_DURATION_SECS) as u128;
+        (self.boost_score as u128 * remaining / BOOST_DURATION_SECS as u128) as u64
+    }
+}
+
+/// Media attached to a post. Clients fetch the bytes from `scheme`/`id` and check
+/// them against `size` and `sha256`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct MediaAttachment {
+    pub scheme: MediaScheme,        // 1 byte
+    pub id: String,                 // 4 + 128 bytes — Arweave tx ID, IPFS CID or https URL
+    pub mime_type: String,          // 4 + 32 bytes — e.g. "image/png"
+    pub size: u64,                  // 8 bytes — content length in bytes
+    pub sha256: [u8; 32],           // 32 bytes — sha256 of the content
+}
+
+impl MediaAttachment {
+    /// Largest serialized attachment.
+    pub const SPACE: usize = 1 + (4 + 128) + (4 + 32) + 8 + 32; // 209 bytes
+
+    pub fn space(&self) -> usize {
+        1 + (4 + self.id.len()) + (4 + self.mime_type.len()) + 8 + 32
+    }
+}
+
+/// Optional parts of a new post, shared by `create_post_with_options` and
+/// `create_compressed_post_with_options`.
+/// `PostOptions::default()` is a plain text post.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct PostOptions {
+    pub attachments: Vec<MediaAttachment>, // up to 4
+    pub mentions: Vec<Pubkey>,             // up to 5 profile authorities
+    pub tags: Vec<String>,                 // up to 3 hashtags
+    pub mention_proof: ValidityProof,      // regular posts only, when mentions are set
+    pub mention_tree_index: u8,            // regular posts only: output tree for the mentions
+    pub expires_at: Option<i64>,           // regular posts only
+    pub crank_reward: u64,                 // regular posts only; requires expires_at
+}
+
+#[account]
+pub struct FollowAccount {
+    pub follower: Pubkey,           // 32 bytes
+    pub following: Pubkey,          // 32 bytes
+    pub created_at: i64,            // 8 bytes
+}
+
+/// Hashtag topic. Created on first use by a tagged post.
+#[account]
+pub struct Topic {
+    pub tag: String,                // 4 + 32 bytes — normalized (lowercase ASCII)
+    pub post_count: u64,            // 8 bytes
+    pub follower_count: u64,        // 8 bytes
+    pub last_post_at: i64,          // 8 bytes
+    pub created_at: i64,            // 8 bytes
+}
+
+impl Topic {
+    pub const SPACE: usize = 8 + (4 + 32) + 8 + 8 + 8 + 8; // 76 bytes
+}
+
+#[account]
+pub struct TopicFollow {
+    pub follower: Pubkey,           // 32 bytes
+    pub topic: Pubkey,              // 32 bytes
+    pub created_at: i64,            // 8 bytes
+}
+
+#[account]
+pub struct Like {
+    pub user: Pubkey,               // 32 bytes
+    pub post: Pubkey,               // 32 bytes
+    pub created_at: i64,            // 8 bytes
+}
+
+/// A reaction to a post. Stored at the Like PDA address (`["like", user, post]`), so a
+/// user has either a Like or a Reaction per post, never both.
+#[account]
+pub struct Reaction {
+    pub user: Pubkey,               // 32 bytes
+    pub post: Pubkey,               // 32 bytes
+    pub kind: ReactionKind,         // 1 byte
+    pub created_at: i64,            // 8 bytes — when the current kind was set
+}
+
+impl Reaction {
+    pub const SPACE: usize = 8 + 32 + 32 + 1 + 8; // 81 bytes
+}
+
+/// Long-form article header. The body lives in ArticleChunk accounts.
+#[account]
+pub struct Article {
+    pub author: Pubkey,             // 32 bytes
+    pub article_id: u64,            // 8 bytes — from profile.post_count
+    pub title: String,              // 4 + 128 bytes
+    pub total_len: u32,             // 4 bytes — declared body length
+    pub chunk_count: u16,           // 2 bytes — declared number of chunks
+    pub chunks_written: u16,        // 2 bytes
+    pub bytes_written: u32,         // 4 bytes
+    pub body_hash: [u8; 32],        // 32 bytes — sha256 of the full body
+    pub finalized: bool,            // 1 byte
+    pub created_at: i64,            // 8 bytes
+    pub finalized_at: i64,          // 8 bytes
+}
+
+// Article space: 8 + 32 + 8 + (4 + 128) + 4 + 2 + 2 + 4 + 32 + 1 + 8 + 8 = 241 bytes
+
+/// One append-only piece of an article body. Sized to its data.
+#[account]
+pub struct ArticleChunk {
+    pub article: Pubkey,            // 32 bytes
+    pub index: u16,                 // 2 bytes
+    pub data: Vec<u8>,              // 4 + up to 900 bytes
+}
+
+/// Left behind by `close_post`, `expire_post` and `compress_post` so references to a deleted post can be resolved.
+#[account]
+pub struct PostTombstone {
+    pub post: Pubkey,               // 32 bytes — the closed Post PDA
+    pub author: Pubkey,             // 32 bytes
+    pub post_id: u64,               // 8 bytes
+    pub deleted_at: i64,            // 8 bytes
+}
+
+// PostTombstone space: 8 + 32 + 32 + 8 + 8 = 88 bytes
+
+#[account]
+pub struct RepostAccount {
+    pub reposter: Pubkey,           // 32 bytes
+    pub post: Pubkey,               // 32 bytes
+    pub created_at: i64,            // 8 bytes
+}
+
+// RepostAccount space: 8 + 32 + 32 + 8 = 80 bytes
+
+/// Program treasury. Holds boost payments until the admin withdraws them.
+#[account]
+pub struct Treasury {
+    pub admin: Pubkey,              // 32 bytes — only key allowed to withdraw
+    pub total_boosted: u64,         // 8 bytes — lamports ever paid in
+    pub total_withdrawn: u64,       // 8 bytes — lamports ever withdrawn
+}
+
+// Treasury space: 8 + 32 + 8 + 8 = 56 bytes
+
+/// A booster's spend on one post.
+#[account]
+pub struct BoostReceipt {
+    pub booster: Pubkey,            // 32 bytes
+    pub post: Pubkey,               // 32 bytes
+    pub total_lamports: u64,        // 8 bytes
+    pub boost_count: u32,           // 4 bytes
+    pub first_boosted_at: i64,      // 8 bytes
+    pub last_boosted_at: i64,       // 8 bytes
+}
+
+// BoostReceipt space: 8 + 32 + 32 + 8 + 4 + 8 + 8 = 100 bytes
+
+/// A booster's spend across all posts, for leaderboards and spend caps.
+#[account]
+pub struct BoosterStats {
+    pub booster: Pubkey,            // 32 bytes
+    pub total_lamports: u64,        // 8 bytes
+    pub boost_count: u32,           // 4 bytes
+    pub posts_boosted: u32,         // 4 bytes — number of BoostReceipts
+    pub first_boosted_at: i64,      // 8 bytes
+    pub last_boosted_at: i64,       // 8 bytes
+}
+
+// BoosterStats space: 8 + 32 + 8 + 4 + 4 + 8 + 8 = 72 bytes
+
+/// Community board. Posts are regular Posts with `community` set.
+#[account]
+pub struct Community {
+    pub slug: String,               // 4 + 32 bytes — unique handle, PDA seed
+    pub description: String,        // 4 + 256 bytes
+    pub creator: Pubkey,            // 32 bytes — always a moderator
+    pub moderators: Vec<Pubkey>,    // 4 + 5 * 32 bytes
+    pub join_policy: JoinPolicy,    // 1 byte
+    pub post_count: u64,            // 8 bytes
+    pub member_count: u64,          // 8 bytes — approved members
+    pub created_at: i64,            // 8 bytes
+}
+
+impl Community {
+    pub const SPACE: usize =
+        8 + (4 + 32) + (4 + 256) + 32 + (4 + MAX_MODERATORS * 32) + 1 + 8 + 8 + 8; // 525 bytes
+
+    pub fn is_moderator(&self, key: &Pubkey) -> bool {
+        self.creator == *key || self.moderators.contains(key)
+    }
+}
+
+#[account]
+pub struct Membership {
+    pub community: Pubkey,          // 32 bytes
+    pub member: Pubkey,             // 32 bytes
+    pub approved: bool,             // 1 byte — false while waiting for approval
+    pub joined_at: i64,             // 8 bytes
+    pub payer: Pubkey,              // 32 bytes — paid the rent, gets it back on removal
+}
+
+// Membership space: 8 + 32 + 32 + 1 + 8 + 32 = 113 bytes
+
+/// A profile banned from a community. While it exists `join_community` fails.
+#[account]
+pub struct Ban {
+    pub community: Pubkey,          // 32 bytes
+    pub member: Pubkey,             // 32 bytes — banned profile's authority
+    pub banned_by: Pubkey,          // 32 bytes — moderator's profile authority
+    pub banned_at: i64,             // 8 bytes
+}
+
+// Ban space: 8 + 32 + 32 + 32 + 8 = 112 bytes
+
+/// Poll attached to a post. `tallies[i]` counts votes for `options[i]`.
+#[account]
+pub struct Poll {
+    pub post: Pubkey,               // 32 bytes
+    pub creator: Pubkey,            // 32 bytes
+    pub options: Vec<String>,       // 4 + 8 * (4 + 50) bytes
+    pub tallies: Vec<u64>,          // 4 + 8 * 8 bytes
+    pub total_votes: u64,           // 8 bytes
+    pub closes_at: i64,             // 8 bytes — 0 = no close time
+    pub created_at: i64,            // 8 bytes
+}
+
+impl Poll {
+    pub const SPACE: usize =
+        8 + 32 + 32 + (4 + MAX_POLL_OPTIONS * (4 + 50)) + (4 + MAX_POLL_OPTIONS * 8) + 8 + 8 + 8; // 600 bytes
+}
+
+#[account]
+pub struct Vote {
+    pub voter: Pubkey,              // 32 bytes
+    pub poll: Pubkey,               // 32 bytes
+    pub option: u8,                 // 1 byte
+    pub created_at: i64,            // 8 bytes
+}
+
+// Vote space: 8 + 32 + 32 + 1 + 8 = 81 bytes
+
+#[account]
+pub struct Referral {
+    pub referred: Pubkey,           // 32 bytes — who was referred
+    pub referrer: Pubkey,           // 32 bytes — who referred them
+    pub created_at: i64,            // 8 bytes
+}
+
+// Referral space: 8 + 32 + 32 + 8 = 80 bytes
+
+#[account]
+pub struct ReferrerStats {
+    pub authority: Pubkey,          // 32 bytes — the referrer
     pub referral_count: u64,        // 8 bytes — total referrals
 }
 
-// ReferrerStats space: 8 + 32 + 8 = 48 bytes
-
-#[account]
-pub struct BotClaim {
-    pub owner: Pubkey,              // 32 bytes — human wallet (passkey-verified)
-    pub bot: Pubkey,                // 32 bytes — bot profile authority
-    pub claimed_at: i64,            // 8 bytes
+// ReferrerStats space: 8 + 32 + 8 = 48 bytes
+
+#[account]
+pub struct BotClaim {
+    pub owner: Pubkey,              // 32 bytes — human wallet (passkey-verified)
+    pub bot: Pubkey,                // 32 bytes — bot profile authority
+    pub claimed_at: i64,            // 8 bytes
+}
+
+// BotClaim space: 8 + 32 + 32 + 8 = 80 bytes
+
+#[account]
+pub struct HumanClaim {
+    pub owner: Pubkey,              // 32 bytes — human wallet
+    pub bot: Pubkey,                // 32 bytes — which bot they claimed
+    pub claimed_at: i64,            // 8 bytes
+}
+
+// HumanClaim space: 8 + 32 + 32 + 8 = 80 bytes
+
+/// Compressed post stored via ZK Compression (Light Protocol).
+/// No rent required — stored as a hash in a state Merkle tree.
+/// Like `Post`, fields are only ever appended; see `migrate_compressed_post`.
+#[derive(Clone, Debug, Default, BorshSerialize, BorshDeserialize, LightDiscriminator)]
+pub struct CompressedPost {
+    pub author: Pubkey,             // 32 bytes
+    pub content: String,            // variable, max 280 chars
+    pub likes: u64,                 // 8 bytes
+    pub created_at: i64,            // 8 bytes
+    pub post_id: u64,               // 8 bytes
+    pub parent: Pubkey,             // 32 bytes — post replied to (default = top-level post)
+    pub root: Pubkey,               // 32 bytes — first post of the thread (default = top-level post)
+    pub reply_count: u64,           // 8 bytes
+    pub revision: u32,              // 4 bytes — number of edits
+    pub edited_at: i64,             // 8 bytes — last edit (0 = never edited)
+    pub history_hash: [u8; 32],     // 32 bytes — running digest of previous contents
+    pub attachments: Vec<MediaAttachment>, // 4 + up to 4 attachments
+    pub tags: Vec<String>,          // 4 + up to 3 normalized hashtags
+    pub repost_count: u64,          // 8 bytes
+    pub quoted_post: Pubkey,        // 32 bytes — post quoted by this one (default = not a quote)
+    pub quote_count: u64,           // 8 bytes
+    pub compressed_from: Pubkey,    // 32 bytes — Post PDA it was compressed from (default = none)
+    pub signed_by: Pubkey,          // 32 bytes — organization member who signed (default = the author)
+}
+
+impl CompressedPost {
+    /// Decode data written by any earlier layout. Fields the old layout didn't have
+    /// are read from zero padding, i.e. take their default values.
+    pub fn from_legacy_bytes(data: &[u8]) -> Result<Self> {
+        let padding = CompressedPost::default()
+            .try_to_vec()
+            .map_err(|_| error!(ClawbookError::InvalidPost))?
+            .len();
+        let mut padded = data.to_vec();
+        padded.resize(data.len() + padding, 0);
+        CompressedPost::deserialize(&mut padded.as_slice())
+            .map_err(|_| error!(ClawbookError::InvalidPost))
+    }
+}
+
+/// Raw `CompressedPost` data as stored by an older layout. Serializes without a length
+/// prefix so the account hash matches the one in the state tree.
+#[derive(Clone, Debug, Default)]
+pub struct LegacyCompressedPost(pub Vec<u8>);
+
+impl BorshSerialize for LegacyCompressedPost {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        writer.write_all(&self.0)
+    }
+}
+
+impl BorshDeserialize for LegacyCompressedPost {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> std::io::Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+        Ok(Self(data))
+    }
+}
+
+impl LightDiscriminator for LegacyCompressedPost {
+    const LIGHT_DISCRIMINATOR: [u8; 8] = CompressedPost::LIGHT_DISCRIMINATOR;
+    const LIGHT_DISCRIMINATOR_SLICE: &'static [u8] = CompressedPost::LIGHT_DISCRIMINATOR_SLICE;
+}
+
+/// Compressed like record. Address derived from `["compressed_like", user, post_address]`.
+#[derive(Clone, Debug, Default, BorshSerialize, BorshDeserialize, LightDiscriminator)]
+pub struct CompressedLike {
+    pub user: Pubkey,               // 32 bytes
+    pub post: Pubkey,               // 32 bytes — compressed post address
+    pub created_at: i64,            // 8 bytes
+}
+
+/// Compressed mention record, one per mentioned profile. Has no address; recipients
+/// query by owner with a filter on `recipient` (the first field).
+#[derive(Clone, Debug, Default, BorshSerialize, BorshDeserialize, LightDiscriminator)]
+pub struct CompressedMention {
+    pub recipient: Pubkey,          // 32 bytes — mentioned profile authority
+    pub post: Pubkey,               // 32 bytes — Post PDA or compressed post address
+    pub author: Pubkey,             // 32 bytes
+    pub created_at: i64,            // 8 bytes
+}
+
+// === Events ===
+
+/// Final result of a poll, emitted by `close_poll`.
+#[event]
+pub struct PollClosed {
+    pub poll: Pubkey,
+    pub post: Pubkey,
+    pub tallies: Vec<u64>,
+    pub total_votes: u64,
+    pub closed_at: i64,
+}
+
+// === Contexts ===
+
+#[derive(Accounts)]
+#[instruction(username: String)]
+pub struct CreateProfile<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = Profile::SPACE,
+        seeds = [b"profile", authority.key().as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, Profile>,
+    #[account(
+        init,
+        payer = authority,
+        space = Username::SPACE,
+        seeds = [b"username", username.as_bytes()],
+        bump
+    )]
+    pub username_record: Account<'info, Username>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimUsername<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = Username::SPACE,
+        seeds = [b"username", profile.username.as_bytes()],
+        bump
+    )]
+    pub username_record: Account<'info, Username>,
+    #[account(
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump,
+        constraint = profile.signer() == authority.key() @ ClawbookError::NotProfileSigner
+    )]
+    pub profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(content: String)]
+pub struct CreatePost<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = Post::space_for(&content, &[], &[]),
+        seeds = [b"post", profile.authority.as_ref(), &profile.post_count.to_le_bytes()],
+        bump
+    )]
+    pub post: Account<'info, Post>,
+    #[account(
+        mut,
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// `CreatePost`, with the post sized for `options`' attachments and tags.
+#[derive(Accounts)]
+#[instruction(content: String, options: PostOptions)]
+pub struct CreatePostWithOptions<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = Post::space_for(&content, &options.attachments, &options.tags),
+        seeds = [b"post", profile.authority.as_ref(), &profile.post_count.to_le_bytes()],
+        bump
+    )]
+    pub post: Account<'info, Post>,
+    #[account(
+        mut,
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for creating a compressed post via Light Protocol.
+/// Light system program accounts are passed via remaining_accounts.
+#[derive(Accounts)]
+pub struct CreateCompressedPost<'info> {
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, Profile>,
+}
+
+/// Context for converting a Post PDA into a compressed post.
+/// Light system program accounts are passed via remaining_accounts.
+#[derive(Accounts)]
+pub struct CompressPost<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"post", profile.authority.as_ref(), &post.post_id.to_le_bytes()],
+        bump,
+        constraint = post.author == profile.authority @ ClawbookError::NotPostAuthor
+    )]
+    pub post: Account<'info, Post>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 32 + 32 + 8 + 8, // 88 bytes
+        seeds = [b"tombstone", post.key().as_ref()],
+        bump
+    )]
+    pub tombstone: Account<'info, PostTombstone>,
+    #[account(
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(content: String)]
+pub struct ReplyToPost<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = Post::space_for(&content, &[], &[]),
+        seeds = [b"post", profile.authority.as_ref(), &profile.post_count.to_le_bytes()],
+        bump
+    )]
+    pub post: Account<'info, Post>,
+    /// The post being replied to — run `migrate_post` first if it is still v1
+    #[account(mut)]
+    pub parent: Account<'info, Post>,
+    #[account(
+        mut,
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for replying to a compressed post.
+/// Light system program accounts are passed via remaining_accounts.
+#[derive(Accounts)]
+#[instruction(proof: ValidityProof, parent_meta: CompressedAccountMeta, parent: CompressedPost, content: String)]
+pub struct ReplyToCompressedPost<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = Post::space_for(&content, &[], &[]),
+        seeds = [b"post", profile.authority.as_ref(), &profile.post_count.to_le_bytes()],
+        bump
+    )]
+    pub post: Account<'info, Post>,
+    #[account(
+        mut,
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StartArticle<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 32 + 8 + (4 + 128) + 4 + 2 + 2 + 4 + 32 + 1 + 8 + 8, // 241 bytes
+        seeds = [b"article", profile.authority.as_ref(), &profile.post_count.to_le_bytes()],
+        bump
+    )]
+    pub article: Account<'info, Article>,
+    #[account(
+        mut,
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump,
+        constraint = profile.signer() == authority.key() @ ClawbookError::NotProfileSigner
+    )]
+    pub profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(index: u16, data: Vec<u8>)]
+pub struct AppendArticleChunk<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 32 + 2 + 4 + data.len(),
+        seeds = [b"article_chunk", article.key().as_ref(), &index.to_le_bytes()],
+        bump
+    )]
+    pub chunk: Account<'info, ArticleChunk>,
+    #[account(
+        mut,
+        seeds = [b"article", profile.authority.as_ref(), &article.article_id.to_le_bytes()],
+        bump
+    )]
+    pub article: Account<'info, Article>,
+    #[account(
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump,
+        constraint = profile.signer() == authority.key() @ ClawbookError::NotProfileSigner
+    )]
+    pub profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Chunk accounts are passed in order via remaining_accounts.
+#[derive(Accounts)]
+pub struct FinalizeArticle<'info> {
+    #[account(
+        mut,
+        seeds = [b"article", profile.authority.as_ref(), &article.article_id.to_le_bytes()],
+        bump
+    )]
+    pub article: Account<'info, Article>,
+    #[account(
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump,
+        constraint = profile.signer() == authority.key() @ ClawbookError::NotProfileSigner
+    )]
+    pub profile: Account<'info, Profile>,
+    pub authority: Signer<'info>,
+}
+
+/// Chunk accounts are passed in order via remaining_accounts.
+#[derive(Accounts)]
+pub struct AbortArticle<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"article", profile.authority.as_ref(), &article.article_id.to_le_bytes()],
+        bump
+    )]
+    pub article: Account<'info, Article>,
+    #[account(
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump,
+        constraint = profile.signer() == authority.key() @ ClawbookError::NotProfileSigner
+    )]
+    pub profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RecordReferral<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 32 + 32 + 8, // 80 bytes
+        seeds = [b"referral", profile.authority.as_ref()],
+        bump
+    )]
+    pub referral: Account<'info, Referral>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + 32 + 8, // 48 bytes
+        seeds = [b"referrer_stats", referrer_profile.authority.as_ref()],
+        bump
+    )]
+    pub referrer_stats: Account<'info, ReferrerStats>,
+    /// The referred user's profile — must exist
+    #[account(
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump,
+        constraint = profile.signer() == authority.key() @ ClawbookError::NotProfileSigner
+    )]
+    pub profile: Account<'info, Profile>,
+    /// The referrer's profile — must exist
+    pub referrer_profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Follow<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 32 + 32 + 8,
+        seeds = [b"follow", follower_profile.authority.as_ref(), following_profile.authority.as_ref()],
+        bump
+    )]
+    pub follow_account: Account<'info, FollowAccount>,
+    #[account(
+        mut,
+        seeds = [b"profile", follower_profile.authority.as_ref()],
+        bump
+    )]
+    pub follower_profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub following_profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Unfollow<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"follow", follower_profile.authority.as_ref(), following_profile.authority.as_ref()],
+        bump
+    )]
+    pub follow_account: Account<'info, FollowAccount>,
+    #[account(
+        mut,
+        seeds = [b"profile", follower_profile.authority.as_ref()],
+        bump
+    )]
+    pub follower_profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub following_profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FollowTopic<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 32 + 32 + 8,
+        seeds = [b"topic_follow", follower_profile.authority.as_ref(), topic.key().as_ref()],
+        bump
+    )]
+    pub topic_follow: Account<'info, TopicFollow>,
+    #[account(
+        mut,
+        seeds = [b"topic", topic.tag.as_bytes()],
+        bump
+    )]
+    pub topic: Account<'info, Topic>,
+    #[account(
+        seeds = [b"profile", follower_profile.authority.as_ref()],
+        bump,
+        constraint = follower_profile.signer() == authority.key() @ ClawbookError::NotProfileSigner
+    )]
+    pub follower_profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UnfollowTopic<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"topic_follow", profile.authority.as_ref(), topic.key().as_ref()],
+        bump
+    )]
+    pub topic_follow: Account<'info, TopicFollow>,
+    #[account(
+        mut,
+        seeds = [b"topic", topic.tag.as_bytes()],
+        bump
+    )]
+    pub topic: Account<'info, Topic>,
+    #[account(
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump,
+        constraint = profile.signer() == authority.key() @ ClawbookError::NotProfileSigner
+    )]
+    pub profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct LikePost<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 32 + 32 + 8,
+        seeds = [b"like", profile.authority.as_ref(), post.key().as_ref()],
+        bump
+    )]
+    pub like: Account<'info, Like>,
+    #[account(mut)]
+    pub post: Account<'info, Post>,
+    #[account(
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UnlikePost<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"like", profile.authority.as_ref(), post.key().as_ref()],
+        bump
+    )]
+    pub like: Account<'info, Like>,
+    #[account(mut)]
+    pub post: Account<'info, Post>,
+    #[account(
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct React<'info> {
+    /// CHECK: Empty, a Like from `like_post`, or a Reaction — told apart by discriminator
+    /// in `react`. Seeds pin it to this user and post.
+    #[account(
+        mut,
+        seeds = [b"like", profile.authority.as_ref(), post.key().as_ref()],
+        bump
+    )]
+    pub reaction: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub post: Account<'info, Post>,
+    #[account(
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Unreact<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"like", profile.authority.as_ref(), post.key().as_ref()],
+        bump
+    )]
+    pub reaction: Account<'info, Reaction>,
+    #[account(mut)]
+    pub post: Account<'info, Post>,
+    #[account(
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+/// Light system program accounts are passed via remaining_accounts.
+#[derive(Accounts)]
+pub struct LikeCompressedPost<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump,
+        constraint = profile.signer() == authority.key() @ ClawbookError::NotProfileSigner
+    )]
+    pub profile: Account<'info, Profile>,
+}
+
+/// Light system program accounts are passed via remaining_accounts.
+#[derive(Accounts)]
+pub struct UnlikeCompressedPost<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump,
+        constraint = profile.signer() == authority.key() @ ClawbookError::NotProfileSigner
+    )]
+    pub profile: Account<'info, Profile>,
+}
+
+/// Light system program accounts are passed via remaining_accounts.
+#[derive(Accounts)]
+pub struct UpdateCompressedPost<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, Profile>,
+}
+
+/// Light system program accounts are passed via remaining_accounts.
+#[derive(Accounts)]
+pub struct CloseCompressedPost<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, Profile>,
+}
+
+#[derive(Accounts)]
+#[instruction(content: String)]
+pub struct EditPost<'info> {
+    #[account(
+        mut,
+        seeds = [b"post", profile.authority.as_ref(), &post.post_id.to_le_bytes()],
+        bump,
+        constraint = post.author == profile.authority @ ClawbookError::NotPostAuthor,
+        realloc = Post::space_for(&content, &post.attachments, &post.tags),
+        realloc::payer = authority,
+        realloc::zero = false
+    )]
+    pub post: Account<'info, Post>,
+    #[account(
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExpirePost<'info> {
+    #[account(
+        mut,
+        close = author,
+        seeds = [b"post", post.author.as_ref(), &post.post_id.to_le_bytes()],
+        bump
+    )]
+    pub post: Account<'info, Post>,
+    #[account(
+        init,
+        payer = caller,
+        space = 8 + 32 + 32 + 8 + 8, // 88 bytes
+        seeds = [b"tombstone", post.key().as_ref()],
+        bump
+    )]
+    pub tombstone: Account<'info, PostTombstone>,
+    #[account(
+        seeds = [b"profile", post.author.as_ref()],
+        bump
+    )]
+    pub author_profile: Account<'info, Profile>,
+    /// CHECK: Receives the post's rent; see `Post::rent_receiver`.
+    #[account(mut, address = post.rent_receiver(&author_profile))]
+    pub author: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub caller: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClosePost<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"post", profile.authority.as_ref(), &post.post_id.to_le_bytes()],
+        bump,
+        constraint = post.author == profile.authority @ ClawbookError::NotPostAuthor
+    )]
+    pub post: Account<'info, Post>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 32 + 32 + 8 + 8, // 88 bytes
+        seeds = [b"tombstone", post.key().as_ref()],
+        bump
+    )]
+    pub tombstone: Account<'info, PostTombstone>,
+    #[account(
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UnlikeDeletedPost<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"like", profile.authority.as_ref(), tombstone.post.as_ref()],
+        bump
+    )]
+    pub like: Account<'info, Like>,
+    #[account(
+        seeds = [b"tombstone", tombstone.post.as_ref()],
+        bump
+    )]
+    pub tombstone: Account<'info, PostTombstone>,
+    #[account(
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UnreactDeletedPost<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"like", profile.authority.as_ref(), tombstone.post.as_ref()],
+        bump
+    )]
+    pub reaction: Account<'info, Reaction>,
+    #[account(
+        seeds = [b"tombstone", tombstone.post.as_ref()],
+        bump
+    )]
+    pub tombstone: Account<'info, PostTombstone>,
+    #[account(
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Repost<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 32 + 32 + 8, // 80 bytes
+        seeds = [b"repost", profile.authority.as_ref(), post.key().as_ref()],
+        bump
+    )]
+    pub repost: Account<'info, RepostAccount>,
+    #[account(mut)]
+    pub post: Account<'info, Post>,
+    #[account(
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UndoRepost<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"repost", profile.authority.as_ref(), post.key().as_ref()],
+        bump
+    )]
+    pub repost: Account<'info, RepostAccount>,
+    #[account(mut)]
+    pub post: Account<'info, Post>,
+    #[account(
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(content: String)]
+pub struct QuotePost<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = Post::space_for(&content, &[], &[]),
+        seeds = [b"post", profile.authority.as_ref(), &profile.post_count.to_le_bytes()],
+        bump
+    )]
+    pub post: Account<'info, Post>,
+    #[account(mut)]
+    pub quoted_post: Account<'info, Post>,
+    #[account(
+        mut,
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeTreasury<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 32 + 8 + 8,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::Clawbook>,
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(authority.key())
+            @ ClawbookError::NotUpgradeAuthority
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct TreasuryAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump,
+        has_one = admin @ ClawbookError::NotTreasuryAdmin
+    )]
+    pub treasury: Account<'info, Treasury>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct BoostPost<'info> {
+    #[account(mut)]
+    pub post: Account<'info, Post>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + 32 + 32 + 8 + 4 + 8 + 8,
+        seeds = [b"boost", authority.key().as_ref(), post.key().as_ref()],
+        bump
+    )]
+    pub receipt: Account<'info, BoostReceipt>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + 32 + 8 + 4 + 4 + 8 + 8,
+        seeds = [b"booster_stats", authority.key().as_ref()],
+        bump
+    )]
+    pub stats: Account<'info, BoosterStats>,
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(slug: String)]
+pub struct CreateCommunity<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = Community::SPACE,
+        seeds = [b"community", slug.as_bytes()],
+        bump
+    )]
+    pub community: Account<'info, Community>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 32 + 32 + 1 + 8 + 32,
+        seeds = [b"membership", community.key().as_ref(), profile.authority.as_ref()],
+        bump
+    )]
+    pub membership: Account<'info, Membership>,
+    #[account(
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump,
+        constraint = profile.signer() == authority.key() @ ClawbookError::NotProfileSigner
+    )]
+    pub profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetModerators<'info> {
+    #[account(
+        mut,
+        seeds = [b"community", community.slug.as_bytes()],
+        bump,
+        constraint = community.creator == profile.authority @ ClawbookError::NotCommunityCreator
+    )]
+    pub community: Account<'info, Community>,
+    #[account(
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump,
+        constraint = profile.signer() == authority.key() @ ClawbookError::NotProfileSigner
+    )]
+    pub profile: Account<'info, Profile>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct JoinCommunity<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 32 + 32 + 1 + 8 + 32,
+        seeds = [b"membership", community.key().as_ref(), profile.authority.as_ref()],
+        bump
+    )]
+    pub membership: Account<'info, Membership>,
+    /// CHECK: The profile's Ban PDA for this community; must not exist.
+    #[account(
+        seeds = [b"ban", community.key().as_ref(), profile.authority.as_ref()],
+        bump,
+        constraint = ban.data_is_empty() @ ClawbookError::MemberBanned
+    )]
+    pub ban: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"community", community.slug.as_bytes()],
+        bump
+    )]
+    pub community: Account<'info, Community>,
+    #[account(
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveMember<'info> {
+    #[account(
+        mut,
+        seeds = [b"membership", community.key().as_ref(), membership.member.as_ref()],
+        bump
+    )]
+    pub membership: Account<'info, Membership>,
+    #[account(
+        mut,
+        seeds = [b"community", community.slug.as_bytes()],
+        bump,
+        constraint = community.is_moderator(&profile.authority) @ ClawbookError::NotModerator
+    )]
+    pub community: Account<'info, Community>,
+    #[account(
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump,
+        constraint = profile.signer() == authority.key() @ ClawbookError::NotProfileSigner
+    )]
+    pub profile: Account<'info, Profile>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct LeaveCommunity<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"membership", community.key().as_ref(), profile.authority.as_ref()],
+        bump
+    )]
+    pub membership: Account<'info, Membership>,
+    #[account(
+        mut,
+        seeds = [b"community", community.slug.as_bytes()],
+        bump
+    )]
+    pub community: Account<'info, Community>,
+    #[account(
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveMember<'info> {
+    #[account(
+        mut,
+        close = payer,
+        seeds = [b"membership", community.key().as_ref(), membership.member.as_ref()],
+        bump
+    )]
+    pub membership: Account<'info, Membership>,
+    /// CHECK: Receives the membership's rent; must be the key that paid for it.
+    #[account(mut, address = membership.payer)]
+    pub payer: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"community", community.slug.as_bytes()],
+        bump,
+        constraint = community.is_moderator(&profile.authority) @ ClawbookError::NotModerator
+    )]
+    pub community: Account<'info, Community>,
+    #[account(
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump,
+        constraint = profile.signer() == authority.key() @ ClawbookError::NotProfileSigner
+    )]
+    pub profile: Account<'info, Profile>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(member: Pubkey)]
+pub struct BanMember<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 32 + 32 + 32 + 8,
+        seeds = [b"ban", community.key().as_ref(), member.as_ref()],
+        bump
+    )]
+    pub ban: Account<'info, Ban>,
+    /// CHECK: The member's Membership PDA; closed if it exists.
+    #[account(
+        mut,
+        seeds = [b"membership", community.key().as_ref(), member.as_ref()],
+        bump
+    )]
+    pub membership: UncheckedAccount<'info>,
+    /// CHECK: Receives the membership's rent; checked against its `payer` in the handler.
+    #[account(mut)]
+    pub membership_payer: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"community", community.slug.as_bytes()],
+        bump,
+        constraint = community.is_moderator(&profile.authority) @ ClawbookError::NotModerator
+    )]
+    pub community: Account<'info, Community>,
+    #[account(
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump,
+        constraint = profile.signer() == authority.key() @ ClawbookError::NotProfileSigner
+    )]
+    pub profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UnbanMember<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"ban", community.key().as_ref(), ban.member.as_ref()],
+        bump
+    )]
+    pub ban: Account<'info, Ban>,
+    #[account(
+        seeds = [b"community", community.slug.as_bytes()],
+        bump,
+        constraint = community.is_moderator(&profile.authority) @ ClawbookError::NotModerator
+    )]
+    pub community: Account<'info, Community>,
+    #[account(
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump,
+        constraint = profile.signer() == authority.key() @ ClawbookError::NotProfileSigner
+    )]
+    pub profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(content: String)]
+pub struct CreateCommunityPost<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = Post::space_for(&content, &[], &[]),
+        seeds = [b"post", profile.authority.as_ref(), &profile.post_count.to_le_bytes()],
+        bump
+    )]
+    pub post: Account<'info, Post>,
+    #[account(
+        mut,
+        seeds = [b"community", community.slug.as_bytes()],
+        bump
+    )]
+    pub community: Account<'info, Community>,
+    #[account(
+        seeds = [b"membership", community.key().as_ref(), profile.authority.as_ref()],
+        bump,
+        constraint = membership.approved @ ClawbookError::NotCommunityMember
+    )]
+    pub membership: Account<'info, Membership>,
+    #[account(
+        mut,
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ModerateCommunityPost<'info> {
+    #[account(
+        mut,
+        constraint = post.community == community.key() @ ClawbookError::PostNotInCommunity
+    )]
+    pub post: Account<'info, Post>,
+    #[account(
+        seeds = [b"community", community.slug.as_bytes()],
+        bump,
+        constraint = community.is_moderator(&profile.authority) @ ClawbookError::NotModerator
+    )]
+    pub community: Account<'info, Community>,
+    #[account(
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump,
+        constraint = profile.signer() == authority.key() @ ClawbookError::NotProfileSigner
+    )]
+    pub profile: Account<'info, Profile>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CreatePoll<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = Poll::SPACE,
+        seeds = [b"poll", post.key().as_ref()],
+        bump
+    )]
+    pub poll: Account<'info, Poll>,
+    #[account(constraint = post.author == profile.authority @ ClawbookError::NotPostAuthor)]
+    pub post: Account<'info, Post>,
+    #[account(
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump,
+        constraint = profile.signer() == authority.key() @ ClawbookError::NotProfileSigner
+    )]
+    pub profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VotePoll<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 32 + 32 + 1 + 8,
+        seeds = [b"vote", voter_profile.authority.as_ref(), poll.key().as_ref()],
+        bump
+    )]
+    pub vote: Account<'info, Vote>,
+    #[account(
+        mut,
+        seeds = [b"poll", poll.post.as_ref()],
+        bump
+    )]
+    pub poll: Account<'info, Poll>,
+    #[account(
+        seeds = [b"profile", voter_profile.authority.as_ref()],
+        bump,
+        constraint = voter_profile.signer() == authority.key() @ ClawbookError::NotProfileSigner
+    )]
+    pub voter_profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClosePoll<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"poll", poll.post.as_ref()],
+        bump,
+        constraint = poll.creator == profile.authority @ ClawbookError::NotPollCreator
+    )]
+    pub poll: Account<'info, Poll>,
+    #[account(
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump,
+        constraint = profile.signer() == authority.key() @ ClawbookError::NotProfileSigner
+    )]
+    pub profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseVote<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"vote", voter_profile.authority.as_ref(), vote.poll.as_ref()],
+        bump
+    )]
+    pub vote: Account<'info, Vote>,
+    /// CHECK: The vote's Poll PDA, which may no longer exist; checked in the handler.
+    #[account(address = vote.poll)]
+    pub poll: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"profile", voter_profile.authority.as_ref()],
+        bump,
+        constraint = voter_profile.signer() == authority.key() @ ClawbookError::NotProfileSigner
+    )]
+    pub voter_profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct PinPost<'info> {
+    #[account(
+        mut,
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, Profile>,
+    #[account(constraint = post.author == profile.authority @ ClawbookError::NotPostAuthor)]
+    pub post: Account<'info, Post>,
+    pub authority: Signer<'info>,
+}
+
+/// Light system program accounts are passed via remaining_accounts.
+#[derive(Accounts)]
+pub struct PinCompressedPost<'info> {
+    #[account(
+        mut,
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
 }
 
-// BotClaim space: 8 + 32 + 32 + 8 = 80 bytes
-
-#[account]
-pub struct HumanClaim {
-    pub owner: Pubkey,              // 32 bytes — human wallet
-    pub bot: Pubkey,                // 32 bytes — which bot they claimed
-    pub claimed_at: i64,            // 8 bytes
+#[derive(Accounts)]
+pub struct UnpinPost<'info> {
+    #[account(
+        mut,
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, Profile>,
+    pub authority: Signer<'info>,
 }
 
-// HumanClaim space: 8 + 32 + 32 + 8 = 80 bytes
+#[derive(Accounts)]
+pub struct CloseProfile<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump,
+        constraint = profile.signer() == authority.key() @ ClawbookError::NotProfileSigner
+    )]
+    pub profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// CHECK: The profile's Username PDA. Closed in the handler if this profile holds
+    /// it; names of profiles created before the registry may be unclaimed or held by
+    /// someone else and are left alone.
+    #[account(
+        mut,
+        seeds = [b"username", profile.username.as_bytes()],
+        bump
+    )]
+    pub username_record: UncheckedAccount<'info>,
+    /// CHECK: The profile's ProfileMetadata PDA; closed in the handler if it exists.
+    #[account(
+        mut,
+        seeds = [b"profile_metadata", profile.authority.as_ref()],
+        bump
+    )]
+    pub metadata: UncheckedAccount<'info>,
+    /// Pass the signer's AuthorityLink to close it too (rotated profiles only).
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"authority_link", authority.key().as_ref()],
+        bump
+    )]
+    pub authority_link: Option<Account<'info, AuthorityLink>>,
+}
 
-/// Compressed post stored via ZK Compression (Light Protocol).
-/// No rent required — stored as a hash in a state Merkle tree.
-#[derive(Clone, Debug, Default, BorshSerialize, BorshDeserialize, LightDiscriminator)]
-pub struct CompressedPost {
-    pub author: Pubkey,             // 32 bytes
-    pub content: String,            // variable, max ~280 chars
-    pub likes: u64,                 // 8 bytes
-    pub created_at: i64,            // 8 bytes
-    pub post_id: u64,               // 8 bytes
+#[derive(Accounts)]
+#[instruction(username: String)]
+pub struct ReleaseOrphanedUsername<'info> {
+    #[account(
+        mut,
+        close = payer,
+        seeds = [b"username", username.as_bytes()],
+        bump
+    )]
+    pub username_record: Account<'info, Username>,
+    /// CHECK: The holder's Profile PDA; must be gone or use another username.
+    #[account(
+        seeds = [b"profile", username_record.authority.as_ref()],
+        bump
+    )]
+    pub profile: UncheckedAccount<'info>,
+    /// CHECK: Receives the record's rent; must be the key that paid for it.
+    #[account(mut, address = username_record.payer)]
+    pub payer: UncheckedAccount<'info>,
 }
 
-// === Contexts ===
+#[derive(Accounts)]
+pub struct UpdateProfile<'info> {
+    #[account(
+        mut,
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump,
+        constraint = profile.signer() == authority.key() @ ClawbookError::NotProfileSigner
+    )]
+    pub profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    // Only needed when the username changes.
+    /// CHECK: Username PDA of the current name; closed if held by this profile.
+    #[account(
+        mut,
+        seeds = [b"username", profile.username.as_bytes()],
+        bump
+    )]
+    pub old_username_record: Option<UncheckedAccount<'info>>,
+    /// CHECK: Username PDA of the new name; address and emptiness checked in the handler.
+    #[account(mut)]
+    pub new_username_record: Option<UncheckedAccount<'info>>,
+    pub system_program: Option<Program<'info, System>>,
+}
 
 #[derive(Accounts)]
-#[instruction(username: String)]
-pub struct CreateProfile<'info> {
+pub struct RotateAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump,
+        constraint = profile.signer() == authority.key() @ ClawbookError::NotProfileSigner
+    )]
+    pub profile: Account<'info, Profile>,
     #[account(
         init,
         payer = authority,
-        space = 8 + 32 + (4 + 32) + (4 + 256) + (4 + 128) + 1 + 32 + 1 + 8 + 8 + 8 + 8, // 534 bytes
-        seeds = [b"profile", authority.key().as_ref()],
+        space = AuthorityLink::SPACE,
+        seeds = [b"authority_link", new_authority.key().as_ref()],
         bump
     )]
-    pub profile: Account<'info, Profile>,
+    pub authority_link: Account<'info, AuthorityLink>,
+    /// The current signer's link — required if the profile was rotated before
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"authority_link", authority.key().as_ref()],
+        bump
+    )]
+    pub previous_link: Option<Account<'info, AuthorityLink>>,
     #[account(mut)]
     pub authority: Signer<'info>,
+    pub new_authority: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct CreatePost<'info> {
+#[instruction(session_key: Pubkey)]
+pub struct CreateSession<'info> {
     #[account(
         init,
         payer = authority,
-        space = 8 + 32 + (4 + 280) + 8 + 8 + 8,
-        seeds = [b"post", authority.key().as_ref(), &profile.post_count.to_le_bytes()],
+        space = SessionKey::SPACE,
+        seeds = [b"session", profile.key().as_ref(), session_key.as_ref()],
         bump
     )]
-    pub post: Account<'info, Post>,
+    pub session: Account<'info, SessionKey>,
     #[account(
-        mut,
-        seeds = [b"profile", authority.key().as_ref()],
-        bump
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump,
+        constraint = profile.signer() == authority.key() @ ClawbookError::NotProfileSigner
     )]
     pub profile: Account<'info, Profile>,
     #[account(mut)]
@@ -497,152 +5086,259 @@ pub struct CreatePost<'info> {
     pub system_program: Program<'info, System>,
 }
 
-/// Context for creating a compressed post via Light Protocol.
-/// Light system program accounts are passed via remaining_accounts.
 #[derive(Accounts)]
-pub struct CreateCompressedPost<'info> {
-    #[account(mut)]
-    pub fee_payer: Signer<'info>,
+pub struct RevokeSession<'info> {
     #[account(
         mut,
-        seeds = [b"profile", fee_payer.key().as_ref()],
+        close = authority,
+        seeds = [b"session", profile.key().as_ref(), session.session_key.as_ref()],
+        bump
+    )]
+    pub session: Account<'info, SessionKey>,
+    #[account(
+        seeds = [b"profile", profile.authority.as_ref()],
         bump,
+        constraint = profile.signer() == authority.key() @ ClawbookError::NotProfileSigner
     )]
     pub profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct RecordReferral<'info> {
+#[instruction(username: String)]
+pub struct CreateOrganization<'info> {
     #[account(
         init,
         payer = authority,
-        space = 8 + 32 + 32 + 8, // 80 bytes
-        seeds = [b"referral", authority.key().as_ref()],
+        space = Organization::SPACE,
+        seeds = [b"organization", username.as_bytes()],
         bump
     )]
-    pub referral: Account<'info, Referral>,
+    pub organization: Account<'info, Organization>,
     #[account(
-        init_if_needed,
+        init,
         payer = authority,
-        space = 8 + 32 + 8, // 48 bytes
-        seeds = [b"referrer_stats", referrer_profile.authority.as_ref()],
+        space = Profile::SPACE,
+        seeds = [b"profile", organization.key().as_ref()],
         bump
     )]
-    pub referrer_stats: Account<'info, ReferrerStats>,
-    /// The referred user's profile — must exist
+    pub profile: Account<'info, Profile>,
     #[account(
-        seeds = [b"profile", authority.key().as_ref()],
+        init,
+        payer = authority,
+        space = Username::SPACE,
+        seeds = [b"username", username.as_bytes()],
         bump
     )]
-    pub profile: Account<'info, Profile>,
-    /// The referrer's profile — must exist
-    pub referrer_profile: Account<'info, Profile>,
+    pub username_record: Account<'info, Username>,
+    #[account(
+        init,
+        payer = authority,
+        space = Member::SPACE,
+        seeds = [b"member", profile.key().as_ref(), authority.key().as_ref()],
+        bump
+    )]
+    pub owner_member: Account<'info, Member>,
     #[account(mut)]
     pub authority: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct Follow<'info> {
+pub struct AddOrgMember<'info> {
+    #[account(
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump,
+        constraint = profile.account_type == AccountType::Organization @ ClawbookError::NotOrganization
+    )]
+    pub profile: Account<'info, Profile>,
+    #[account(
+        seeds = [b"member", profile.key().as_ref(), authority.key().as_ref()],
+        bump,
+        constraint = admin_member.role >= MemberRole::Admin @ ClawbookError::NotOrgAdmin
+    )]
+    pub admin_member: Account<'info, Member>,
+    #[account(mut, address = profile.authority)]
+    pub organization: Account<'info, Organization>,
     #[account(
         init,
         payer = authority,
-        space = 8 + 32 + 32 + 8,
-        seeds = [b"follow", authority.key().as_ref(), following_profile.authority.as_ref()],
+        space = Member::SPACE,
+        seeds = [b"member", profile.key().as_ref(), new_member.key().as_ref()],
         bump
     )]
-    pub follow_account: Account<'info, FollowAccount>,
+    pub member: Account<'info, Member>,
+    /// CHECK: The wallet being added; only its key is used.
+    pub new_member: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveOrgMember<'info> {
+    #[account(
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump,
+        constraint = profile.account_type == AccountType::Organization @ ClawbookError::NotOrganization
+    )]
+    pub profile: Account<'info, Profile>,
+    #[account(
+        seeds = [b"member", profile.key().as_ref(), authority.key().as_ref()],
+        bump,
+        constraint = admin_member.role >= MemberRole::Admin @ ClawbookError::NotOrgAdmin
+    )]
+    pub admin_member: Account<'info, Member>,
+    #[account(mut, address = profile.authority)]
+    pub organization: Account<'info, Organization>,
     #[account(
         mut,
-        seeds = [b"profile", authority.key().as_ref()],
+        close = payer,
+        seeds = [b"member", profile.key().as_ref(), member.member.as_ref()],
         bump
     )]
-    pub follower_profile: Account<'info, Profile>,
-    #[account(mut)]
-    pub following_profile: Account<'info, Profile>,
-    #[account(mut)]
+    pub member: Account<'info, Member>,
+    /// CHECK: Receives the Member's rent; must be the key that paid for it.
+    #[account(mut, address = member.payer)]
+    pub payer: UncheckedAccount<'info>,
     pub authority: Signer<'info>,
-    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct Unfollow<'info> {
+pub struct TransferOrgOwnership<'info> {
+    #[account(
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump,
+        constraint = profile.account_type == AccountType::Organization @ ClawbookError::NotOrganization
+    )]
+    pub profile: Account<'info, Profile>,
     #[account(
         mut,
-        close = authority,
-        seeds = [b"follow", authority.key().as_ref(), following_profile.authority.as_ref()],
-        bump
+        seeds = [b"member", profile.key().as_ref(), authority.key().as_ref()],
+        bump,
+        constraint = owner_member.role == MemberRole::Owner @ ClawbookError::NotOrgOwner
     )]
-    pub follow_account: Account<'info, FollowAccount>,
+    pub owner_member: Account<'info, Member>,
     #[account(
         mut,
-        seeds = [b"profile", authority.key().as_ref()],
-        bump
+        seeds = [b"member", profile.key().as_ref(), new_owner_member.member.as_ref()],
+        bump,
+        constraint = new_owner_member.member != authority.key() @ ClawbookError::InvalidMemberRole
     )]
-    pub follower_profile: Account<'info, Profile>,
-    #[account(mut)]
-    pub following_profile: Account<'info, Profile>,
-    #[account(mut)]
+    pub new_owner_member: Account<'info, Member>,
     pub authority: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct LikePost<'info> {
+pub struct UpdateOrgProfile<'info> {
     #[account(
-        init,
-        payer = authority,
-        space = 8 + 32 + 32 + 8,
-        seeds = [b"like", authority.key().as_ref(), post.key().as_ref()],
-        bump
+        mut,
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump,
+        constraint = profile.account_type == AccountType::Organization @ ClawbookError::NotOrganization
     )]
-    pub like: Account<'info, Like>,
-    #[account(mut)]
-    pub post: Account<'info, Post>,
+    pub profile: Account<'info, Profile>,
+    #[account(
+        seeds = [b"member", profile.key().as_ref(), authority.key().as_ref()],
+        bump,
+        constraint = admin_member.role >= MemberRole::Admin @ ClawbookError::NotOrgAdmin
+    )]
+    pub admin_member: Account<'info, Member>,
     #[account(mut)]
     pub authority: Signer<'info>,
-    pub system_program: Program<'info, System>,
+    // Only needed when the username changes.
+    /// CHECK: Username PDA of the current name; closed if held by this profile.
+    #[account(
+        mut,
+        seeds = [b"username", profile.username.as_bytes()],
+        bump
+    )]
+    pub old_username_record: Option<UncheckedAccount<'info>>,
+    /// CHECK: Username PDA of the new name; address and emptiness checked in the handler.
+    #[account(mut)]
+    pub new_username_record: Option<UncheckedAccount<'info>>,
+    pub system_program: Option<Program<'info, System>>,
 }
 
 #[derive(Accounts)]
-pub struct UnlikePost<'info> {
+#[instruction(content: String)]
+pub struct CreateOrgPost<'info> {
     #[account(
-        mut,
-        close = authority,
-        seeds = [b"like", authority.key().as_ref(), post.key().as_ref()],
+        init,
+        payer = authority,
+        space = Post::space_for(&content, &[], &[]),
+        seeds = [b"post", profile.authority.as_ref(), &profile.post_count.to_le_bytes()],
         bump
     )]
-    pub like: Account<'info, Like>,
-    #[account(mut)]
     pub post: Account<'info, Post>,
+    #[account(
+        mut,
+        seeds = [b"profile", profile.authority.as_ref()],
+        bump,
+        constraint = profile.account_type == AccountType::Organization @ ClawbookError::NotOrganization
+    )]
+    pub profile: Account<'info, Profile>,
+    /// Any role may post
+    #[account(
+        seeds = [b"member", profile.key().as_ref(), authority.key().as_ref()],
+        bump
+    )]
+    pub member: Account<'info, Member>,
     #[account(mut)]
     pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct CloseProfile<'info> {
+pub struct CloseOrganization<'info> {
     #[account(
         mut,
         close = authority,
-        seeds = [b"profile", authority.key().as_ref()],
+        seeds = [b"profile", profile.authority.as_ref()],
         bump,
-        has_one = authority
+        constraint = profile.account_type == AccountType::Organization @ ClawbookError::NotOrganization
     )]
     pub profile: Account<'info, Profile>,
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"member", profile.key().as_ref(), authority.key().as_ref()],
+        bump,
+        constraint = owner_member.role == MemberRole::Owner @ ClawbookError::NotOrgOwner
+    )]
+    pub owner_member: Account<'info, Member>,
+    #[account(mut, close = authority, address = profile.authority)]
+    pub organization: Account<'info, Organization>,
+    /// CHECK: The organization's Username PDA; closed in the handler.
+    #[account(
+        mut,
+        seeds = [b"username", profile.username.as_bytes()],
+        bump
+    )]
+    pub username_record: UncheckedAccount<'info>,
     #[account(mut)]
     pub authority: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct UpdateProfile<'info> {
+pub struct UpdateMetadata<'info> {
+    /// CHECK: ProfileMetadata PDA; created, resized and (de)serialized in the handler.
     #[account(
         mut,
-        seeds = [b"profile", authority.key().as_ref()],
+        seeds = [b"profile_metadata", profile.authority.as_ref()],
+        bump
+    )]
+    pub metadata: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"profile", profile.authority.as_ref()],
         bump,
-        has_one = authority
+        constraint = profile.signer() == authority.key() @ ClawbookError::NotProfileSigner
     )]
     pub profile: Account<'info, Profile>,
     #[account(mut)]
     pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
@@ -679,20 +5375,35 @@ pub struct ClaimBot<'info> {
 
 #[derive(Accounts)]
 pub struct MigrateProfile<'info> {
-    /// CHECK: Read raw bytes — account may be in old format (402 bytes, no pfp field)
+    /// CHECK: Read raw bytes — account may be in an old format (368, 402, 534 or 630 bytes)
     /// that cannot be deserialized as the current Profile struct.
-    /// PDA seeds verify it's a valid profile address.
-    #[account(
-        mut,
-        seeds = [b"profile", authority.key().as_ref()],
-        bump
-    )]
+    /// The handler checks its address against the stored `authority` and the signer.
+    #[account(mut, owner = crate::ID)]
     pub profile: UncheckedAccount<'info>,
     #[account(mut)]
     pub authority: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct MigratePost<'info> {
+    /// CHECK: Read raw bytes — account may be in an old, shorter Post format that
+    /// cannot be deserialized as the current Post struct.
+    /// Owner and discriminator are checked in the handler.
+    #[account(mut, owner = crate::ID)]
+    pub post: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Light system program accounts are passed via remaining_accounts.
+#[derive(Accounts)]
+pub struct MigrateCompressedPost<'info> {
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+}
+
 // === Errors ===
 
 #[error_code]
@@ -709,7 +5420,7 @@ pub enum ClawbookError {
     InvalidBotProof,
     #[msg("Light Protocol CPI error")]
     LightCpiError,
-    #[msg("Invalid profile format — expected 402-byte old format for migration")]
+    #[msg("Invalid profile format — expected 368-, 402-, 534- or 630-byte old format for migration")]
     InvalidProfile,
     #[msg("This bot has already been claimed by another human")]
     BotAlreadyClaimed,
@@ -717,4 +5428,150 @@ pub enum ClawbookError {
     HumanAlreadyClaimedBot,
     #[msg("Profile is not a bot — only bot profiles can be claimed")]
     InvalidBotProfile,
+    #[msg("Invalid post format — account is not a Clawbook post")]
+    InvalidPost,
+    #[msg("Only the post author can do this")]
+    NotPostAuthor,
+    #[msg("Hashing failed")]
+    HashError,
+    #[msg("Like record does not belong to this user and post")]
+    InvalidLikeRecord,
+    #[msg("Batch must contain between 1 and 4 posts")]
+    InvalidBatchSize,
+    #[msg("Title must be 128 characters or less")]
+    TitleTooLong,
+    #[msg("Article must have 1-24 chunks of at most 900 bytes each")]
+    ArticleTooLong,
+    #[msg("Article is finalized and can no longer change")]
+    ArticleFinalized,
+    #[msg("Invalid article chunk — wrong article, out of order, or exceeds the declared length")]
+    InvalidArticleChunk,
+    #[msg("Article is missing chunks or bytes")]
+    ArticleIncomplete,
+    #[msg("Article body does not match the declared sha256 hash")]
+    ArticleHashMismatch,
+    #[msg("A post can have at most 4 attachments")]
+    TooManyAttachments,
+    #[msg("Attachment identifier is malformed for its storage scheme")]
+    InvalidAttachmentId,
+    #[msg("Attachment MIME type must be a lowercase type/subtype of 32 characters or less")]
+    InvalidMimeType,
+    #[msg("Attachment size must be greater than zero")]
+    InvalidAttachmentSize,
+    #[msg("Attachment sha256 hash must not be empty")]
+    InvalidAttachmentHash,
+    #[msg("A post can mention at most 5 profiles")]
+    TooManyMentions,
+    #[msg("Mentioned profile is missing, duplicated or does not match its authority")]
+    InvalidMention,
+    #[msg("A post can have at most 3 hashtags")]
+    TooManyTags,
+    #[msg("Hashtags must be 1-32 characters of a-z, 0-9 or _ with no duplicates")]
+    InvalidTag,
+    #[msg("Topic account does not match its hashtag")]
+    InvalidTopic,
+    #[msg("A profile can pin at most 3 posts")]
+    TooManyPinnedPosts,
+    #[msg("Post is already pinned")]
+    PostAlreadyPinned,
+    #[msg("Post is not pinned")]
+    PostNotPinned,
+    #[msg("Poll must have 2-8 options of 1-50 characters")]
+    InvalidPollOptions,
+    #[msg("Poll close time must be in the future")]
+    InvalidPollCloseTime,
+    #[msg("Poll is closed")]
+    PollClosed,
+    #[msg("Poll option does not exist")]
+    InvalidPollOption,
+    #[msg("Poll can't be closed before its close time")]
+    PollStillOpen,
+    #[msg("Only the poll creator can do this")]
+    NotPollCreator,
+    #[msg("Expiry must be in the future, and a crank reward needs an expiry")]
+    InvalidExpiry,
+    #[msg("Post has no expiry or has not expired yet")]
+    PostNotExpired,
+    #[msg("Text is not valid UTF-8")]
+    InvalidUtf8,
+    #[msg("Text must not contain control characters other than newline")]
+    ControlCharacter,
+    #[msg("Text must not contain bidirectional override or isolate characters")]
+    BidiControlCharacter,
+    #[msg("Username must be 1-32 characters of a-z, 0-9 or _")]
+    InvalidUsername,
+    #[msg("Community slug must be 1-32 characters of a-z, 0-9, _ or -")]
+    InvalidCommunitySlug,
+    #[msg("Description must be 256 characters or less")]
+    DescriptionTooLong,
+    #[msg("A community can have at most 5 moderators besides its creator")]
+    TooManyModerators,
+    #[msg("Only the community creator can do this")]
+    NotCommunityCreator,
+    #[msg("Only a community moderator can do this")]
+    NotModerator,
+    #[msg("Only approved community members can post")]
+    NotCommunityMember,
+    #[msg("Membership is already approved")]
+    AlreadyApproved,
+    #[msg("Post does not belong to this community")]
+    PostNotInCommunity,
+    #[msg("Only the program upgrade authority can do this")]
+    NotUpgradeAuthority,
+    #[msg("Only the treasury admin can do this")]
+    NotTreasuryAdmin,
+    #[msg("Treasury balance too low")]
+    InsufficientTreasuryBalance,
+    #[msg("Boost must be at least 0.001 SOL")]
+    BoostTooSmall,
+    #[msg("Username is already taken")]
+    UsernameTaken,
+    #[msg("Changing the username requires the old and new Username PDAs")]
+    MissingUsernameRecord,
+    #[msg("Signer does not control this profile")]
+    NotProfileSigner,
+    #[msg("New authority must differ from the profile's current and original keys")]
+    InvalidNewAuthority,
+    #[msg("Rotating a rotated profile requires the current signer's AuthorityLink")]
+    MissingAuthorityLink,
+    #[msg("Session permissions must be a non-empty set of SESSION_* bits")]
+    InvalidSessionPermissions,
+    #[msg("Session key must differ from the profile's own keys")]
+    InvalidSessionKey,
+    #[msg("Session key has expired or was revoked by an authority rotation")]
+    SessionExpired,
+    #[msg("Session key is not allowed to sign this instruction")]
+    SessionNotPermitted,
+    #[msg("Session key has used up today's quota")]
+    SessionQuotaExceeded,
+    #[msg("Profile is not an organization")]
+    NotOrganization,
+    #[msg("Only organization admins and owners can do this")]
+    NotOrgAdmin,
+    #[msg("Only the organization owner can do this")]
+    NotOrgOwner,
+    #[msg("Ownership only moves through transfer_org_ownership, to another member")]
+    InvalidMemberRole,
+    #[msg("Metadata key must be 1-32 characters of a-z, 0-9 or _")]
+    InvalidMetadataKey,
+    #[msg("Metadata value is too long or not an https:// URL")]
+    InvalidMetadataValue,
+    #[msg("Profile metadata is full")]
+    TooManyMetadataEntries,
+    #[msg("Metadata key not found")]
+    MetadataKeyNotFound,
+    #[msg("Crank reward exceeds the maximum")]
+    CrankRewardTooHigh,
+    #[msg("Post already liked")]
+    AlreadyLiked,
+    #[msg("The username's profile still exists and uses it")]
+    ProfileStillExists,
+    #[msg("Profile is banned from this community")]
+    MemberBanned,
+    #[msg("Moderators can't be removed or banned")]
+    MemberIsModerator,
+    #[msg("Remove every other member before closing the organization")]
+    OrganizationHasMembers,
+    #[msg("Rent must go back to the account's payer")]
+    InvalidRentReceiver,
 }
@@ -0,0 +1,63 @@
+//! `Post::space_for` must match the serialized size exactly: posts are allocated with
+//! it at creation and resized with it on edit.
+
+use anchor_lang::prelude::*;
+use clawbook::{MediaAttachment, MediaScheme, Post, MAX_ATTACHMENTS, MAX_TAGS};
+
+fn serialized_len(post: &Post) -> usize {
+    let mut data = Vec::new();
+    post.try_serialize(&mut data).unwrap();
+    data.len()
+}
+
+fn attachment(id_len: usize, mime_len: usize) -> MediaAttachment {
+    MediaAttachment {
+        scheme: MediaScheme::Https,
+        id: "i".repeat(id_len),
+        mime_type: "m".repeat(mime_len),
+        size: 1,
+        sha256: [1; 32],
+    }
+}
+
+fn post(content: &str, attachments: Vec<MediaAttachment>, tags: Vec<String>) -> Post {
+    Post {
+        content: content.to_string(),
+        attachments,
+        tags,
+        ..Post::default()
+    }
+}
+
+#[test]
+fn plain_post_is_fixed_space_plus_content() {
+    let post = post("Hello Clawbook! 🦞", Vec::new(), Vec::new());
+    assert_eq!(post.space(), Post::FIXED_SPACE + post.content.len());
+    assert_eq!(post.space(), serialized_len(&post));
+}
+
+#[test]
+fn matches_serialized_len_with_attachments_and_tags() {
+    let post = post(
+        "Look at this",
+        vec![attachment(43, 9), attachment(128, 32)],
+        vec!["solana".to_string(), "ai".to_string()],
+    );
+    assert_eq!(post.space(), serialized_len(&post));
+    assert_eq!(
+        Post::space_for(&post.content, &post.attachments, &post.tags),
+        post.space()
+    );
+}
+
+#[test]
+fn largest_post_matches_the_old_fixed_size() {
+    let post = post(
+        &"a".repeat(560),
+        vec![attachment(128, 32); MAX_ATTACHMENTS],
+        vec!["t".repeat(32); MAX_TAGS],
+    );
+    assert_eq!(post.attachments[0].space(), MediaAttachment::SPACE);
+    assert_eq!(post.space(), 1881);
+    assert_eq!(post.space(), serialized_len(&post));
+}
@@ -0,0 +1,151 @@
+//! `migrate_profile` byte layouts: every historical Profile layout must upgrade to the
+//! current one with the fields it had preserved and the rest set to their defaults.
+
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use clawbook::{AccountType, Profile, ProfileVersion, MAX_PINNED_POSTS};
+
+fn sample() -> Profile {
+    Profile {
+        authority: Pubkey::new_from_array([7; 32]),
+        username: "molty".to_string(),
+        bio: "Shell-shocked agent 🦞".to_string(),
+        pfp: "https://example.com/molty.png".to_string(),
+        account_type: AccountType::Bot,
+        bot_proof_hash: [9; 32],
+        verified: true,
+        post_count: 42,
+        follower_count: 7,
+        following_count: 3,
+        created_at: 1_700_000_000,
+        pinned_posts: [Pubkey::new_from_array([5; 32]); MAX_PINNED_POSTS],
+        controller: Pubkey::new_from_array([6; 32]),
+    }
+}
+
+fn push_string(data: &mut Vec<u8>, value: &str) {
+    data.extend_from_slice(&(value.len() as u32).to_le_bytes());
+    data.extend_from_slice(value.as_bytes());
+}
+
+/// Serialize `profile` as `version` wrote it, padded to that version's account size.
+fn encode(version: ProfileVersion, profile: &Profile) -> Vec<u8> {
+    let mut data = Profile::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(profile.authority.as_ref());
+    push_string(&mut data, &profile.username);
+    push_string(&mut data, &profile.bio);
+    if version >= ProfileVersion::V3 {
+        push_string(&mut data, &profile.pfp);
+    }
+    if version >= ProfileVersion::V2 {
+        data.push(profile.account_type as u8);
+        data.extend_from_slice(&profile.bot_proof_hash);
+        data.push(profile.verified as u8);
+    }
+    data.extend_from_slice(&profile.post_count.to_le_bytes());
+    data.extend_from_slice(&profile.follower_count.to_le_bytes());
+    data.extend_from_slice(&profile.following_count.to_le_bytes());
+    data.extend_from_slice(&profile.created_at.to_le_bytes());
+    if version >= ProfileVersion::V4 {
+        for pin in profile.pinned_posts.iter() {
+            data.extend_from_slice(pin.as_ref());
+        }
+    }
+    if version >= ProfileVersion::V5 {
+        data.extend_from_slice(profile.controller.as_ref());
+    }
+    assert!(data.len() <= version.size());
+    data.resize(version.size(), 0);
+    data
+}
+
+/// `profile` as seen after migrating from `version`: fields `version` lacked are defaults.
+fn migrated(version: ProfileVersion, profile: &Profile) -> Vec<u8> {
+    let mut expected = profile.clone();
+    if version < ProfileVersion::V2 {
+        expected.account_type = AccountType::Human;
+        expected.bot_proof_hash = [0; 32];
+        expected.verified = false;
+    }
+    if version < ProfileVersion::V3 {
+        expected.pfp = String::new();
+    }
+    if version < ProfileVersion::V4 {
+        expected.pinned_posts = [Pubkey::default(); MAX_PINNED_POSTS];
+    }
+    if version < ProfileVersion::V5 {
+        expected.controller = Pubkey::default();
+    }
+    encode(ProfileVersion::CURRENT, &expected)
+}
+
+const HISTORICAL: [(ProfileVersion, usize); 4] = [
+    (ProfileVersion::V1, 368),
+    (ProfileVersion::V2, 402),
+    (ProfileVersion::V3, 534),
+    (ProfileVersion::V4, 630),
+];
+
+#[test]
+fn detects_every_layout_by_size() {
+    for (version, size) in HISTORICAL {
+        assert_eq!(version.size(), size);
+        assert_eq!(ProfileVersion::from_size(size), Some(version));
+    }
+    assert_eq!(ProfileVersion::CURRENT.size(), Profile::SPACE);
+    assert_eq!(ProfileVersion::from_size(400), None);
+}
+
+#[test]
+fn upgrades_every_layout_to_current() {
+    let profile = sample();
+    for (version, _) in HISTORICAL {
+        let upgraded = ProfileVersion::upgrade(&encode(version, &profile)).unwrap();
+        assert_eq!(upgraded, migrated(version, &profile), "from {version:?}");
+
+        let decoded = Profile::try_deserialize(&mut upgraded.as_slice()).unwrap();
+        assert_eq!(decoded.username, profile.username, "from {version:?}");
+        assert_eq!(decoded.post_count, profile.post_count, "from {version:?}");
+        assert_eq!(decoded.created_at, profile.created_at, "from {version:?}");
+    }
+}
+
+#[test]
+fn upgrades_full_length_strings() {
+    let mut profile = sample();
+    profile.username = "a".repeat(32);
+    profile.bio = "b".repeat(256);
+    profile.pfp = "c".repeat(128);
+    for (version, _) in HISTORICAL {
+        let upgraded = ProfileVersion::upgrade(&encode(version, &profile)).unwrap();
+        assert_eq!(upgraded, migrated(version, &profile), "from {version:?}");
+    }
+}
+
+#[test]
+fn drops_stale_bytes_after_the_fields() {
+    let profile = sample();
+    for (version, size) in HISTORICAL {
+        let mut data = encode(version, &profile);
+        // Leftovers from an `update_profile` that shortened a string
+        let last = data.len() - 1;
+        data[last] = 0xff;
+        let upgraded = ProfileVersion::upgrade(&data).unwrap();
+        assert_eq!(upgraded.len(), Profile::SPACE);
+        assert_eq!(upgraded, migrated(version, &profile), "from {version:?} ({size} bytes)");
+    }
+}
+
+#[test]
+fn rejects_unknown_sizes_and_accounts() {
+    assert!(ProfileVersion::upgrade(&[0; 400]).is_err());
+
+    let mut data = encode(ProfileVersion::V2, &sample());
+    data[0] ^= 1;
+    assert!(ProfileVersion::upgrade(&data).is_err());
+
+    // A string length running past the end of the account
+    let mut data = encode(ProfileVersion::V1, &sample());
+    data[40..44].copy_from_slice(&u32::MAX.to_le_bytes());
+    assert!(ProfileVersion::upgrade(&data).is_err());
+}
@@ -1,3 +1,4 @@
+use clawbook_validation::ValidationError;
 use pinocchio::program_error::ProgramError;
 
 #[repr(u32)]
@@ -23,6 +24,27 @@ pub enum Error {
     HumanAlreadyClaimedBot = 6008,
     /// Profile is not a bot — only bot profiles can be claimed
     InvalidBotProfile = 6009,
+    /// Text is not valid UTF-8
+    InvalidUtf8 = 6042,
+    /// Text must not contain control characters other than newline
+    ControlCharacter = 6043,
+    /// Text must not contain bidirectional override or isolate characters
+    BidiControlCharacter = 6044,
+    /// Username must be 1-32 characters of a-z, 0-9 or _
+    InvalidUsername = 6045,
+}
+
+impl Error {
+    /// Map a shared validation failure. `too_long` is the field's own length error.
+    pub fn from_validation(err: ValidationError, too_long: Error) -> Self {
+        match err {
+            ValidationError::TooLong => too_long,
+            ValidationError::InvalidUtf8 => Error::InvalidUtf8,
+            ValidationError::ControlCharacter => Error::ControlCharacter,
+            ValidationError::BidiControl => Error::BidiControlCharacter,
+            ValidationError::InvalidUsername => Error::InvalidUsername,
+        }
+    }
 }
 
 impl From<Error> for ProgramError {
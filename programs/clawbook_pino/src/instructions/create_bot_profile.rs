@@ -9,6 +9,7 @@ use pinocchio::{
     sysvars::{clock::Clock, Sysvar},
 };
 
+use clawbook_validation::{validate_bio, validate_pfp, validate_username};
 use crate::error::Error;
 use crate::helpers::*;
 use crate::state::Profile;
@@ -58,18 +59,10 @@ pub fn create_bot_profile(
     // Transformed instruction logic
     // Deserialize state accounts
     let mut profile_state = Profile::from_account_info_mut(profile)?;
-    if !(username.len () <= 32) {
-    return Err(Error::UsernameTooLong.into());
-    }
-    ;
-    if !(bio.len () <= 256) {
-    return Err(Error::BioTooLong.into());
-    }
-    ;
-    if !(pfp.len () <= 128) {
-    return Err(Error::PfpTooLong.into());
-    }
-    ;
+    validate_username(username.as_bytes())
+        .map_err(|e| Error::from_validation(e, Error::UsernameTooLong))?;
+    validate_bio(bio.as_bytes()).map_err(|e| Error::from_validation(e, Error::BioTooLong))?;
+    validate_pfp(pfp.as_bytes()).map_err(|e| Error::from_validation(e, Error::PfpTooLong))?;
     let empty_hash = [0u8 ; 32] ;
     if !(bot_proof_hash  != *empty_hash) {
     return Err(Error::InvalidBotProof.into());
@@ -9,6 +9,7 @@ use pinocchio::{
     sysvars::{clock::Clock, Sysvar},
 };
 
+use clawbook_validation::validate_content;
 use crate::error::Error;
 use crate::helpers::*;
 use crate::state::Post;
@@ -73,10 +74,8 @@ pub fn create_post(
     // Deserialize state accounts
     let mut post_state = Post::from_account_info_mut(post)?;
     let profile_state = Profile::from_account_info_mut(profile)?;
-    if !(content.len () <= 280) {
-    return Err(Error::ContentTooLong.into());
-    }
-    ;
+    validate_content(content.as_bytes())
+        .map_err(|e| Error::from_validation(e, Error::ContentTooLong))?;
     post_state.author = *authority.key () ;
     post_state.content = content ;
     post_state.likes = 0 ;
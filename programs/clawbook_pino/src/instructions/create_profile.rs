@@ -9,6 +9,7 @@ use pinocchio::{
     sysvars::{clock::Clock, Sysvar},
 };
 
+use clawbook_validation::{validate_bio, validate_pfp, validate_username};
 use crate::error::Error;
 use crate::helpers::*;
 use crate::state::Profile;
@@ -57,18 +58,10 @@ pub fn create_profile(
     // Transformed instruction logic
     // Deserialize state accounts
     let mut profile_state = Profile::from_account_info_mut(profile)?;
-    if !(username.len () <= 32) {
-    return Err(Error::UsernameTooLong.into());
-    }
-    ;
-    if !(bio.len () <= 256) {
-    return Err(Error::BioTooLong.into());
-    }
-    ;
-    if !(pfp.len () <= 128) {
-    return Err(Error::PfpTooLong.into());
-    }
-    ;
+    validate_username(username.as_bytes())
+        .map_err(|e| Error::from_validation(e, Error::UsernameTooLong))?;
+    validate_bio(bio.as_bytes()).map_err(|e| Error::from_validation(e, Error::BioTooLong))?;
+    validate_pfp(pfp.as_bytes()).map_err(|e| Error::from_validation(e, Error::PfpTooLong))?;
     profile_state.authority = *authority.key () ;
     profile_state.username = username ;
     profile_state.bio = bio ;
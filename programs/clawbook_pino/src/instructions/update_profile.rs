@@ -9,6 +9,7 @@ use pinocchio::{
     sysvars::{clock::Clock, Sysvar},
 };
 
+use clawbook_validation::{validate_bio, validate_pfp, validate_username};
 use crate::error::Error;
 use crate::helpers::*;
 use crate::state::Profile;
@@ -58,14 +59,18 @@ pub fn update_profile(
     // Transformed instruction logic
     // Deserialize state accounts
     let mut profile_state = Profile::from_account_info_mut(profile)?;
-    if let Some (new_username) = username { if !(new_username.len () <= 32) {
-    return Err(Error::UsernameTooLong.into());
-    }; profile_state.username = new_username ; }
-    if let Some (new_bio) = bio { if !(new_bio.len () <= 256) {
-    return Err(Error::BioTooLong.into());
-    }; profile_state.bio = new_bio ; }
-    if let Some (new_pfp) = pfp { if !(new_pfp.len () <= 128) {
-    return Err(Error::PfpTooLong.into());
-    }; profile_state.pfp = new_pfp ; }
+    if let Some (new_username) = username {
+        validate_username(new_username.as_bytes())
+            .map_err(|e| Error::from_validation(e, Error::UsernameTooLong))?;
+        profile_state.username = new_username ;
+    }
+    if let Some (new_bio) = bio {
+        validate_bio(new_bio.as_bytes()).map_err(|e| Error::from_validation(e, Error::BioTooLong))?;
+        profile_state.bio = new_bio ;
+    }
+    if let Some (new_pfp) = pfp {
+        validate_pfp(new_pfp.as_bytes()).map_err(|e| Error::from_validation(e, Error::PfpTooLong))?;
+        profile_state.pfp = new_pfp ;
+    }
     Ok(())
 }
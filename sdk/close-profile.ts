@@ -41,12 +41,26 @@ async function closeProfile(keypairPath: string) {
   const username = data.subarray(44, 44 + usernameLen).toString("utf-8");
   console.log("Username:", username);
 
+  // The profile's Username PDA is required; the name is released if this profile holds it
+  const [usernamePda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("username"), Buffer.from(username)],
+    PROGRAM_ID
+  );
+  // ProfileMetadata PDA, closed too if the profile has one
+  const [metadataPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("profile_metadata"), authority.publicKey.toBuffer()],
+    PROGRAM_ID
+  );
+  const keys = [
+    { pubkey: profilePda, isSigner: false, isWritable: true },
+    { pubkey: authority.publicKey, isSigner: true, isWritable: true },
+    { pubkey: usernamePda, isSigner: false, isWritable: true },
+    { pubkey: metadataPda, isSigner: false, isWritable: true },
+  ];
+
   // Build close_profile instruction
   const instruction = new TransactionInstruction({
-    keys: [
-      { pubkey: profilePda, isSigner: false, isWritable: true },
-      { pubkey: authority.publicKey, isSigner: true, isWritable: true },
-    ],
+    keys,
     programId: PROGRAM_ID,
     data: CLOSE_PROFILE_DISCRIMINATOR,
   });
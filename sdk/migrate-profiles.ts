@@ -1,6 +1,6 @@
 #!/usr/bin/env ts-node
 /**
- * Migrate all old-format profiles (402 bytes, no pfp field) to the new format (534 bytes, with pfp).
+ * Migrate all old-format profiles (368, 402, 534 or 630 bytes) to the current format (662 bytes).
  * 
  * Old profiles cause OOM errors when instructions try to deserialize them with the new schema.
  * This script finds all old profiles and calls the migrate_profile instruction.
@@ -43,11 +43,16 @@ async function main() {
     "2tULpabuwwcjsAUWhXMcDFnCj3QLDJ7r5dAxH8S1FLbE"
   );
 
-  // Find old profile accounts (402 bytes)
+  // Find old profile accounts (v1-v4 layouts)
   console.log("📋 Scanning for old-format profiles...");
-  const oldProfiles = await connection.getProgramAccounts(PROGRAM_ID, {
-    filters: [{ dataSize: 402 }],
-  });
+  const OLD_PROFILE_SIZES = [368, 402, 534, 630];
+  const oldProfiles = (
+    await Promise.all(
+      OLD_PROFILE_SIZES.map((dataSize) =>
+        connection.getProgramAccounts(PROGRAM_ID, { filters: [{ dataSize }] })
+      )
+    )
+  ).flat();
 
   if (oldProfiles.length === 0) {
     console.log("✅ No old-format profiles found. All profiles are up to date!");
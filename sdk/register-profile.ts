@@ -82,10 +82,16 @@ async function main() {
     pfpBytes,
   ]);
 
+  const [usernamePDA] = PublicKey.findProgramAddressSync(
+    [Buffer.from("username"), usernameBytes],
+    PROGRAM_ID
+  );
+
   // Create instruction
   const instruction = new TransactionInstruction({
     keys: [
       { pubkey: profilePDA, isSigner: false, isWritable: true },
+      { pubkey: usernamePDA, isSigner: false, isWritable: true },
       { pubkey: wallet.publicKey, isSigner: true, isWritable: true },
       { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
     ],
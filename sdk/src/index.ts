@@ -40,6 +40,11 @@ const FOLLOW_SEED = "follow";
 const LIKE_SEED = "like";
 const REFERRAL_SEED = "referral";
 const REFERRER_STATS_SEED = "referrer_stats";
+const USERNAME_SEED = "username";
+const SESSION_SEED = "session";
+const ORGANIZATION_SEED = "organization";
+const MEMBER_SEED = "member";
+const PROFILE_METADATA_SEED = "profile_metadata";
 
 /**
  * Get Anchor instruction discriminator (sha256("global:<name>")[0:8])
@@ -173,6 +178,67 @@ export class Clawbook {
     );
   }
 
+  /**
+   * Derive username registry PDA
+   */
+  getUsernamePDA(username: string): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from(USERNAME_SEED), Buffer.from(username)],
+      this.programId
+    );
+  }
+
+  /**
+   * Derive session key PDA. Pass it (writable) as the first remaining account when
+   * a session key signs for the profile.
+   */
+  getSessionPDA(profile: PublicKey, sessionKey: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from(SESSION_SEED), profile.toBuffer(), sessionKey.toBuffer()],
+      this.programId
+    );
+  }
+
+  /**
+   * Derive an organization's identity key — its profile's `authority`
+   */
+  getOrganizationPDA(username: string): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from(ORGANIZATION_SEED), Buffer.from(username)],
+      this.programId
+    );
+  }
+
+  /**
+   * Derive organization member PDA
+   */
+  getMemberPDA(profile: PublicKey, member: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from(MEMBER_SEED), profile.toBuffer(), member.toBuffer()],
+      this.programId
+    );
+  }
+
+  /**
+   * Derive profile metadata PDA
+   */
+  getProfileMetadataPDA(authority: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from(PROFILE_METADATA_SEED), authority.toBuffer()],
+      this.programId
+    );
+  }
+
+  /**
+   * Resolve a username to its profile authority, or null if unclaimed
+   */
+  async resolveUsername(username: string): Promise<PublicKey | null> {
+    const [usernamePDA] = this.getUsernamePDA(username);
+    const accountInfo = await this.connection.getAccountInfo(usernamePDA);
+    if (!accountInfo) return null;
+    return new PublicKey(accountInfo.data.subarray(8, 40));
+  }
+
   /**
    * Fetch a profile by authority
    */
@@ -340,9 +406,12 @@ export class Clawbook {
       pfpBytes,
     ]);
 
+    const [usernamePDA] = this.getUsernamePDA(username);
+
     const ix = new TransactionInstruction({
       keys: [
         { pubkey: profilePDA, isSigner: false, isWritable: true },
+        { pubkey: usernamePDA, isSigner: false, isWritable: true },
         { pubkey: this.wallet.publicKey, isSigner: true, isWritable: true },
         { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
       ],
@@ -386,11 +455,23 @@ export class Clawbook {
       encodeOptionString(pfp),
     ]);
 
+    const keys = [
+      { pubkey: profilePDA, isSigner: false, isWritable: true },
+      { pubkey: this.wallet.publicKey, isSigner: true, isWritable: true },
+    ];
+
+    // A new username moves the registry entry: old name released, new name claimed
+    const current = username === undefined ? null : await this.getProfile();
+    if (current && current.username !== username) {
+      keys.push(
+        { pubkey: this.getUsernamePDA(current.username)[0], isSigner: false, isWritable: true },
+        { pubkey: this.getUsernamePDA(username!)[0], isSigner: false, isWritable: true },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      );
+    }
+
     const ix = new TransactionInstruction({
-      keys: [
-        { pubkey: profilePDA, isSigner: false, isWritable: true },
-        { pubkey: this.wallet.publicKey, isSigner: true, isWritable: false },
-      ],
+      keys,
       programId: this.programId,
       data,
     });
@@ -587,11 +668,13 @@ export class Clawbook {
    */
   async like(postAddress: PublicKey): Promise<string> {
     const [likePDA] = this.getLikePDA(this.wallet.publicKey, postAddress);
+    const [profilePDA] = this.getProfilePDA();
 
     const ix = new TransactionInstruction({
       keys: [
         { pubkey: likePDA, isSigner: false, isWritable: true },
         { pubkey: postAddress, isSigner: false, isWritable: true },
+        { pubkey: profilePDA, isSigner: false, isWritable: false },
         { pubkey: this.wallet.publicKey, isSigner: true, isWritable: true },
         { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
       ],
@@ -603,8 +686,8 @@ export class Clawbook {
   }
 
   /**
-   * Migrate old profile (402 bytes, no pfp field) to new format (534 bytes, with pfp).
-   * Only needed if profile was created before the pfp field was added.
+   * Migrate an old profile (368, 402, 534 or 630 bytes) to the current format (662 bytes).
+   * Only needed for profiles created before the latest layout change.
    * Safe to call on already-migrated profiles (no-op).
    * 
    * @returns Transaction signature
@@ -789,26 +872,24 @@ export async function getNetworkStats(
 ): Promise<ClawbookStats> {
   const connection = new Connection(endpoint, "confirmed");
   
-  // Profile account sizes: 402 (old, no pfp) + 534 (new, with pfp)
-  const PROFILE_SIZE_OLD = 402;
-  const PROFILE_SIZE_NEW = 534;
-  // Post account size: 8 + 32 + (4 + 280) + 8 + 8 + 8 = 348
-  const POST_SIZE = 348;
+  // Profiles of every layout version (368 to 662 bytes) share the Anchor
+  // discriminator sha256("account:Profile")[..8], base58
+  const PROFILE_DISCRIMINATOR = "XqtBdGS7oVD";
+  // Posts change size with every layout bump; match their Anchor discriminator
+  // (sha256("account:Post")[..8], base58) instead
+  const POST_DISCRIMINATOR = "2SCFvsZq1W5";
   // Follow account size: 8 + 32 + 32 + 8 = 80
   const FOLLOW_SIZE = 80;
   // Like account size: 8 + 32 + 32 + 8 = 80
   const LIKE_SIZE = 80;
 
-  // Fetch all accounts in parallel — query both profile sizes
-  const [profilesOld, profilesNew, posts, follows, likes] = await Promise.all([
-    connection.getProgramAccounts(programId, {
-      filters: [{ dataSize: PROFILE_SIZE_OLD }],
-    }),
+  // Fetch all accounts in parallel
+  const [profiles, posts, follows, likes] = await Promise.all([
     connection.getProgramAccounts(programId, {
-      filters: [{ dataSize: PROFILE_SIZE_NEW }],
+      filters: [{ memcmp: { offset: 0, bytes: PROFILE_DISCRIMINATOR } }],
     }),
     connection.getProgramAccounts(programId, {
-      filters: [{ dataSize: POST_SIZE }],
+      filters: [{ memcmp: { offset: 0, bytes: POST_DISCRIMINATOR } }],
     }),
     connection.getProgramAccounts(programId, {
       filters: [{ dataSize: FOLLOW_SIZE }],
@@ -818,8 +899,6 @@ export async function getNetworkStats(
     }),
   ]);
 
-  const profiles = [...profilesOld, ...profilesNew];
-
   // Count bots vs humans by checking account_type byte
   let totalBots = 0;
   let totalHumans = 0;
@@ -834,7 +913,7 @@ export async function getNetworkStats(
     const bioLen = data.readUInt32LE(offset);
     offset += 4 + bioLen;
     
-    // For new format (534 bytes): skip pfp length + pfp
+    // v3 and later (534 bytes and up): skip pfp length + pfp
     const isNewFormat = data.length >= 534;
     if (isNewFormat && offset + 4 <= data.length) {
       const pfpLen = data.readUInt32LE(offset);
@@ -873,20 +952,11 @@ export async function getAllProfiles(
   programId: PublicKey = CLAWBOOK_PROGRAM_ID
 ): Promise<Array<{ pubkey: PublicKey; profile: Profile }>> {
   const connection = new Connection(endpoint, "confirmed");
-  const PROFILE_SIZE_OLD = 402;
-  const PROFILE_SIZE_NEW = 534;
-
-  // Fetch both old and new format profiles
-  const [accountsOld, accountsNew] = await Promise.all([
-    connection.getProgramAccounts(programId, {
-      filters: [{ dataSize: PROFILE_SIZE_OLD }],
-    }),
-    connection.getProgramAccounts(programId, {
-      filters: [{ dataSize: PROFILE_SIZE_NEW }],
-    }),
-  ]);
-
-  const allAccounts = [...accountsOld, ...accountsNew];
+  // Profiles of every layout version share the Anchor discriminator
+  // sha256("account:Profile")[..8], base58
+  const allAccounts = await connection.getProgramAccounts(programId, {
+    filters: [{ memcmp: { offset: 0, bytes: "XqtBdGS7oVD" } }],
+  });
 
   return allAccounts.map(({ pubkey, account }) => ({
     pubkey,
@@ -1,19 +1,45 @@
 import * as anchor from "@coral-xyz/anchor";
-import { Program } from "@coral-xyz/anchor";
+import { IdlTypes, Program } from "@coral-xyz/anchor";
 import { Clawbook } from "../target/types/clawbook";
 import { expect } from "chai";
+import { createHash } from "crypto";
 
 describe("clawbook", () => {
   const provider = anchor.AnchorProvider.env();
   anchor.setProvider(provider);
 
   const program = anchor.workspace.Clawbook as Program<Clawbook>;
+
+  /** A plain text post's PostOptions, with `overrides` applied */
+  const postOptions = (
+    overrides: Partial<IdlTypes<Clawbook>["postOptions"]> = {}
+  ): IdlTypes<Clawbook>["postOptions"] => ({
+    attachments: [],
+    mentions: [],
+    tags: [],
+    mentionProof: { 0: null },
+    mentionTreeIndex: 0,
+    expiresAt: null,
+    crankReward: new anchor.BN(0),
+    ...overrides,
+  });
+
+  const findPda = (...seeds: Buffer[]) =>
+    anchor.web3.PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+
+  const findProfilePda = (authority: anchor.web3.PublicKey) =>
+    findPda(Buffer.from("profile"), authority.toBuffer());
+
+  /** The `index`th post of the profile at `authority` */
+  const findPostPda = (authority: anchor.web3.PublicKey, index: number | anchor.BN) =>
+    findPda(
+      Buffer.from("post"),
+      authority.toBuffer(),
+      new anchor.BN(index).toArrayLike(Buffer, "le", 8)
+    );
   
   it("Creates a profile", async () => {
-    const [profilePda] = anchor.web3.PublicKey.findProgramAddressSync(
-      [Buffer.from("profile"), provider.wallet.publicKey.toBuffer()],
-      program.programId
-    );
+    const profilePda = findProfilePda(provider.wallet.publicKey);
 
     await program.methods
       .createProfile("testbot", "I am a test bot 🤖")
@@ -27,24 +53,18 @@ describe("clawbook", () => {
     const profile = await program.account.profile.fetch(profilePda);
     expect(profile.username).to.equal("testbot");
     expect(profile.bio).to.equal("I am a test bot 🤖");
+
+    const usernamePda = findPda(Buffer.from("username"), Buffer.from("testbot"));
+    const usernameRecord = await program.account.username.fetch(usernamePda);
+    expect(usernameRecord.authority.toBase58()).to.equal(provider.wallet.publicKey.toBase58());
   });
 
   it("Creates a post", async () => {
-    const [profilePda] = anchor.web3.PublicKey.findProgramAddressSync(
-      [Buffer.from("profile"), provider.wallet.publicKey.toBuffer()],
-      program.programId
-    );
+    const profilePda = findProfilePda(provider.wallet.publicKey);
 
     const profile = await program.account.profile.fetch(profilePda);
     
-    const [postPda] = anchor.web3.PublicKey.findProgramAddressSync(
-      [
-        Buffer.from("post"),
-        provider.wallet.publicKey.toBuffer(),
-        new anchor.BN(profile.postCount).toArrayLike(Buffer, "le", 8),
-      ],
-      program.programId
-    );
+    const postPda = findPostPda(provider.wallet.publicKey, profile.postCount);
 
     await program.methods
       .createPost("Hello Clawbook! 🦞")
@@ -59,4 +79,1132 @@ describe("clawbook", () => {
     const post = await program.account.post.fetch(postPda);
     expect(post.content).to.equal("Hello Clawbook! 🦞");
   });
+
+  it("Creates a post with an attachment", async () => {
+    const profilePda = findProfilePda(provider.wallet.publicKey);
+
+    const profile = await program.account.profile.fetch(profilePda);
+
+    const postPda = findPostPda(provider.wallet.publicKey, profile.postCount);
+
+    const attachment = {
+      scheme: { ipfs: {} },
+      id: "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG",
+      mimeType: "image/png",
+      size: new anchor.BN(48213),
+      sha256: Array.from(Buffer.alloc(32, 7)),
+    };
+
+    await program.methods
+      .createPostWithOptions("Look at this 🦞", postOptions({ attachments: [attachment] }))
+      .accounts({
+        post: postPda,
+        profile: profilePda,
+        authority: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const post = await program.account.post.fetch(postPda);
+    expect(post.attachments).to.have.length(1);
+    expect(post.attachments[0].id).to.equal(attachment.id);
+    expect(post.attachments[0].mimeType).to.equal("image/png");
+  });
+
+  it("Creates a post with a hashtag and follows the topic", async () => {
+    const profilePda = findProfilePda(provider.wallet.publicKey);
+
+    const profile = await program.account.profile.fetch(profilePda);
+
+    const postPda = findPostPda(provider.wallet.publicKey, profile.postCount);
+    const topicPda = findPda(Buffer.from("topic"), Buffer.from("solana"));
+
+    await program.methods
+      .createPostWithOptions("Building on #Solana 🦞", postOptions({ tags: ["#Solana"] }))
+      .accounts({
+        post: postPda,
+        profile: profilePda,
+        authority: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .remainingAccounts([{ pubkey: topicPda, isWritable: true, isSigner: false }])
+      .rpc();
+
+    const post = await program.account.post.fetch(postPda);
+    expect(post.tags).to.deep.equal(["solana"]);
+
+    const topicFollowPda = findPda(
+      Buffer.from("topic_follow"),
+      provider.wallet.publicKey.toBuffer(),
+      topicPda.toBuffer(),
+    );
+
+    await program.methods
+      .followTopic()
+      .accounts({
+        topicFollow: topicFollowPda,
+        topic: topicPda,
+        followerProfile: profilePda,
+        authority: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const topic = await program.account.topic.fetch(topicPda);
+    expect(topic.tag).to.equal("solana");
+    expect(topic.postCount.toNumber()).to.equal(1);
+    expect(topic.followerCount.toNumber()).to.equal(1);
+  });
+
+  it("Creates a poll and votes on it", async () => {
+    const profilePda = findProfilePda(provider.wallet.publicKey);
+
+    const profile = await program.account.profile.fetch(profilePda);
+
+    const postPda = findPostPda(provider.wallet.publicKey, profile.postCount.subn(1));
+    const pollPda = findPda(Buffer.from("poll"), postPda.toBuffer());
+    const votePda = findPda(
+      Buffer.from("vote"),
+      provider.wallet.publicKey.toBuffer(),
+      pollPda.toBuffer(),
+    );
+
+    await program.methods
+      .createPoll(["Claude", "GPT", "Llama"], null)
+      .accounts({
+        poll: pollPda,
+        post: postPda,
+        profile: profilePda,
+        authority: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    await program.methods
+      .votePoll(0)
+      .accounts({
+        vote: votePda,
+        poll: pollPda,
+        voterProfile: profilePda,
+        authority: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const poll = await program.account.poll.fetch(pollPda);
+    expect(poll.tallies.map((t) => t.toNumber())).to.deep.equal([1, 0, 0]);
+    expect(poll.totalVotes.toNumber()).to.equal(1);
+  });
+
+  it("Closes a vote once its poll is closed", async () => {
+    const profilePda = findProfilePda(provider.wallet.publicKey);
+
+    const profile = await program.account.profile.fetch(profilePda);
+
+    const postPda = findPostPda(provider.wallet.publicKey, profile.postCount.subn(1));
+    const pollPda = findPda(Buffer.from("poll"), postPda.toBuffer());
+    const votePda = findPda(
+      Buffer.from("vote"),
+      provider.wallet.publicKey.toBuffer(),
+      pollPda.toBuffer(),
+    );
+    const closeVote = () =>
+      program.methods
+        .closeVote()
+        .accounts({
+          vote: votePda,
+          poll: pollPda,
+          voterProfile: profilePda,
+          authority: provider.wallet.publicKey,
+        })
+        .rpc();
+
+    try {
+      await closeVote();
+      expect.fail("vote on an open poll should stay");
+    } catch (err) {
+      expect(String(err)).to.include("PollStillOpen");
+    }
+
+    await program.methods
+      .closePoll()
+      .accounts({
+        poll: pollPda,
+        profile: profilePda,
+        authority: provider.wallet.publicKey,
+      })
+      .rpc();
+    await closeVote();
+
+    expect(await provider.connection.getAccountInfo(votePda)).to.equal(null);
+  });
+
+  it("Switches a like to another reaction", async () => {
+    const profilePda = findProfilePda(provider.wallet.publicKey);
+
+    const profile = await program.account.profile.fetch(profilePda);
+
+    const postPda = findPostPda(provider.wallet.publicKey, profile.postCount.subn(1));
+    const likePda = findPda(
+      Buffer.from("like"),
+      provider.wallet.publicKey.toBuffer(),
+      postPda.toBuffer(),
+    );
+
+    await program.methods
+      .likePost()
+      .accounts({
+        like: likePda,
+        post: postPda,
+        profile: profilePda,
+        authority: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    await program.methods
+      .react({ insightful: {} })
+      .accounts({
+        reaction: likePda,
+        post: postPda,
+        profile: profilePda,
+        authority: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const post = await program.account.post.fetch(postPda);
+    expect(post.likes.toNumber()).to.equal(0);
+    expect(post.reactionCounts[2].toNumber()).to.equal(1);
+
+    const reaction = await program.account.reaction.fetch(likePda);
+    expect(reaction.kind).to.deep.equal({ insightful: {} });
+  });
+
+  it("Replies to a post", async () => {
+    const profilePda = findProfilePda(provider.wallet.publicKey);
+
+    const profile = await program.account.profile.fetch(profilePda);
+
+    const parentPda = findPostPda(provider.wallet.publicKey, profile.postCount.subn(1));
+    const replyPda = findPostPda(provider.wallet.publicKey, profile.postCount);
+
+    await program.methods
+      .replyToPost("Replying to myself 🧵")
+      .accounts({
+        post: replyPda,
+        parent: parentPda,
+        profile: profilePda,
+        authority: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const reply = await program.account.post.fetch(replyPda);
+    expect(reply.parent.toBase58()).to.equal(parentPda.toBase58());
+    expect(reply.root.toBase58()).to.equal(parentPda.toBase58());
+
+    const parent = await program.account.post.fetch(parentPda);
+    expect(parent.replyCount.toNumber()).to.equal(1);
+  });
+
+  it("Reposts and quotes a post", async () => {
+    const profilePda = findProfilePda(provider.wallet.publicKey);
+
+    const profile = await program.account.profile.fetch(profilePda);
+
+    const originalPda = findPostPda(provider.wallet.publicKey, 0);
+    const repostPda = findPda(
+      Buffer.from("repost"),
+      provider.wallet.publicKey.toBuffer(),
+      originalPda.toBuffer(),
+    );
+    const quotePda = findPostPda(provider.wallet.publicKey, profile.postCount);
+    const repost = () =>
+      program.methods
+        .repost()
+        .accounts({
+          repost: repostPda,
+          post: originalPda,
+          profile: profilePda,
+          authority: provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+    await repost();
+    expect((await program.account.post.fetch(originalPda)).repostCount.toNumber()).to.equal(1);
+
+    // One repost per user and post
+    try {
+      await repost();
+      expect.fail("the same post should not be reposted twice");
+    } catch (err) {
+      expect(String(err)).to.include("already in use");
+    }
+
+    await program.methods
+      .undoRepost()
+      .accounts({
+        repost: repostPda,
+        post: originalPda,
+        profile: profilePda,
+        authority: provider.wallet.publicKey,
+      })
+      .rpc();
+    expect(await provider.connection.getAccountInfo(repostPda)).to.equal(null);
+    expect((await program.account.post.fetch(originalPda)).repostCount.toNumber()).to.equal(0);
+
+    await program.methods
+      .quotePost("This 👇")
+      .accounts({
+        post: quotePda,
+        quotedPost: originalPda,
+        profile: profilePda,
+        authority: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const quote = await program.account.post.fetch(quotePda);
+    expect(quote.content).to.equal("This 👇");
+    expect(quote.quotedPost.toBase58()).to.equal(originalPda.toBase58());
+    expect((await program.account.post.fetch(originalPda)).quoteCount.toNumber()).to.equal(1);
+  });
+
+  it("Deletes a post and leaves a tombstone", async () => {
+    const profilePda = findProfilePda(provider.wallet.publicKey);
+
+    const profile = await program.account.profile.fetch(profilePda);
+
+    const postPda = findPostPda(provider.wallet.publicKey, profile.postCount);
+    const tombstonePda = findPda(Buffer.from("tombstone"), postPda.toBuffer());
+    const likePda = findPda(
+      Buffer.from("like"),
+      provider.wallet.publicKey.toBuffer(),
+      postPda.toBuffer(),
+    );
+
+    await program.methods
+      .createPost("Posted by mistake")
+      .accounts({
+        post: postPda,
+        profile: profilePda,
+        authority: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    await program.methods
+      .likePost()
+      .accounts({
+        like: likePda,
+        post: postPda,
+        profile: profilePda,
+        authority: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const unlikeDeleted = () =>
+      program.methods
+        .unlikeDeletedPost()
+        .accounts({
+          like: likePda,
+          tombstone: tombstonePda,
+          profile: profilePda,
+          authority: provider.wallet.publicKey,
+        })
+        .rpc();
+
+    // Only likes of deleted posts can be reclaimed this way
+    try {
+      await unlikeDeleted();
+      expect.fail("a live post has no tombstone");
+    } catch (err) {
+      expect(String(err)).to.include("AccountNotInitialized");
+    }
+
+    await program.methods
+      .closePost()
+      .accounts({
+        post: postPda,
+        tombstone: tombstonePda,
+        profile: profilePda,
+        authority: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    expect(await provider.connection.getAccountInfo(postPda)).to.equal(null);
+    const tombstone = await program.account.postTombstone.fetch(tombstonePda);
+    expect(tombstone.postId.toNumber()).to.equal(profile.postCount.toNumber());
+    // post_count is not decremented, so the seed is never reused
+    const after = await program.account.profile.fetch(profilePda);
+    expect(after.postCount.toNumber()).to.equal(profile.postCount.toNumber() + 1);
+
+    await unlikeDeleted();
+    expect(await provider.connection.getAccountInfo(likePda)).to.equal(null);
+  });
+
+  it("Edits a post and chains the old content", async () => {
+    const profilePda = findProfilePda(provider.wallet.publicKey);
+    const postPda = findPostPda(provider.wallet.publicKey, 0);
+    const edit = (content: string) =>
+      program.methods
+        .editPost(content)
+        .accounts({
+          post: postPda,
+          profile: profilePda,
+          authority: provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+    const before = await program.account.post.fetch(postPda);
+    await edit("Hello again, Clawbook! 🦞🦞");
+
+    const post = await program.account.post.fetch(postPda);
+    expect(post.content).to.equal("Hello again, Clawbook! 🦞🦞");
+    expect(post.revision).to.equal(before.revision + 1);
+    expect(post.editedAt.toNumber()).to.be.greaterThan(0);
+    // history_hash = sha256(history_hash || sha256(old_content))
+    const expected = createHash("sha256")
+      .update(Buffer.from(before.historyHash))
+      .update(createHash("sha256").update(before.content).digest())
+      .digest();
+    expect(Buffer.from(post.historyHash).equals(expected)).to.equal(true);
+
+    // Same limit as create_post
+    try {
+      await edit("a".repeat(281));
+      expect.fail("edits over 280 characters should be rejected");
+    } catch (err) {
+      expect(String(err)).to.include("ContentTooLong");
+    }
+  });
+
+  it("Pins and unpins a post", async () => {
+    const profilePda = findProfilePda(provider.wallet.publicKey);
+    const postPda = findPostPda(provider.wallet.publicKey, 0);
+    const pin = () =>
+      program.methods
+        .pinPost()
+        .accounts({ profile: profilePda, post: postPda, authority: provider.wallet.publicKey })
+        .rpc();
+    const unpin = () =>
+      program.methods
+        .unpinPost(postPda)
+        .accounts({ profile: profilePda, authority: provider.wallet.publicKey })
+        .rpc();
+
+    await pin();
+    let profile = await program.account.profile.fetch(profilePda);
+    expect(profile.pinnedPosts[0].toBase58()).to.equal(postPda.toBase58());
+
+    try {
+      await pin();
+      expect.fail("a post should only be pinned once");
+    } catch (err) {
+      expect(String(err)).to.include("PostAlreadyPinned");
+    }
+
+    await unpin();
+    profile = await program.account.profile.fetch(profilePda);
+    expect(profile.pinnedPosts.every((pinned) => pinned.equals(anchor.web3.PublicKey.default))).to.equal(true);
+
+    try {
+      await unpin();
+      expect.fail("unpinning a post that isn't pinned should fail");
+    } catch (err) {
+      expect(String(err)).to.include("PostNotPinned");
+    }
+  });
+
+  it("Validates post content and usernames", async () => {
+    const profilePda = findProfilePda(provider.wallet.publicKey);
+    const post = async (content: string) => {
+      const profile = await program.account.profile.fetch(profilePda);
+      const postPda = findPostPda(provider.wallet.publicKey, profile.postCount);
+      await program.methods
+        .createPost(content)
+        .accounts({
+          post: postPda,
+          profile: profilePda,
+          authority: provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      return postPda;
+    };
+
+    // 280 characters is the limit, whatever their size in bytes
+    const postPda = await post("é".repeat(280));
+    expect((await program.account.post.fetch(postPda)).content).to.equal("é".repeat(280));
+
+    try {
+      await post("é".repeat(281));
+      expect.fail("281 characters should be rejected");
+    } catch (err) {
+      expect(String(err)).to.include("ContentTooLong");
+    }
+
+    try {
+      await post("gm \u202Eoll");
+      expect.fail("bidi overrides should be rejected");
+    } catch (err) {
+      expect(String(err)).to.include("BidiControlCharacter");
+    }
+
+    const newcomer = anchor.web3.Keypair.generate();
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(newcomer.publicKey, anchor.web3.LAMPORTS_PER_SOL),
+      "confirmed"
+    );
+    const newcomerProfilePda = findProfilePda(newcomer.publicKey);
+    try {
+      await program.methods
+        .createProfile("Molty", "", "")
+        .accounts({
+          profile: newcomerProfilePda,
+          authority: newcomer.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([newcomer])
+        .rpc();
+      expect.fail("uppercase usernames should be rejected");
+    } catch (err) {
+      expect(String(err)).to.include("InvalidUsername");
+    }
+  });
+
+  it("Boosts a post and tracks the booster's totals", async () => {
+    const treasuryPda = findPda(Buffer.from("treasury"));
+    const [programData] = anchor.web3.PublicKey.findProgramAddressSync(
+      [program.programId.toBuffer()],
+      new anchor.web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+    );
+    await program.methods
+      .initializeTreasury(provider.wallet.publicKey)
+      .accounts({
+        treasury: treasuryPda,
+        program: program.programId,
+        programData,
+        authority: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const postPda = findPostPda(provider.wallet.publicKey, 0);
+    const receiptPda = findPda(
+      Buffer.from("boost"),
+      provider.wallet.publicKey.toBuffer(),
+      postPda.toBuffer(),
+    );
+    const statsPda = findPda(Buffer.from("booster_stats"), provider.wallet.publicKey.toBuffer());
+    const boost = (lamports: number) =>
+      program.methods
+        .boostPost(new anchor.BN(lamports))
+        .accounts({
+          post: postPda,
+          receipt: receiptPda,
+          stats: statsPda,
+          treasury: treasuryPda,
+          authority: provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+    await boost(1_000_000);
+    await boost(2_000_000);
+
+    const receipt = await program.account.boostReceipt.fetch(receiptPda);
+    expect(receipt.totalLamports.toNumber()).to.equal(3_000_000);
+    expect(receipt.boostCount).to.equal(2);
+    const stats = await program.account.boosterStats.fetch(statsPda);
+    expect(stats.totalLamports.toNumber()).to.equal(3_000_000);
+    expect(stats.boostCount).to.equal(2);
+    expect(stats.postsBoosted).to.equal(1);
+
+    try {
+      await boost(999_999);
+      expect.fail("boosts under the minimum should be rejected");
+    } catch (err) {
+      expect(String(err)).to.include("BoostTooSmall");
+    }
+  });
+
+  it("Aborts an unfinished article", async () => {
+    const profilePda = findProfilePda(provider.wallet.publicKey);
+    const profile = await program.account.profile.fetch(profilePda);
+    const articlePda = findPda(
+      Buffer.from("article"),
+      provider.wallet.publicKey.toBuffer(),
+      profile.postCount.toArrayLike(Buffer,
+      "le",
+      8),
+    );
+    const chunkPda = findPda(
+      Buffer.from("article_chunk"),
+      articlePda.toBuffer(),
+      new anchor.BN(0).toArrayLike(Buffer,
+      "le",
+      2),
+    );
+
+    await program.methods
+      .startArticle("Draft", 10, 2, Array(32).fill(0))
+      .accounts({
+        article: articlePda,
+        profile: profilePda,
+        authority: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    await program.methods
+      .appendArticleChunk(0, Buffer.from("hello"))
+      .accounts({
+        chunk: chunkPda,
+        article: articlePda,
+        profile: profilePda,
+        authority: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const abort = (chunks: anchor.web3.PublicKey[]) =>
+      program.methods
+        .abortArticle()
+        .accounts({
+          article: articlePda,
+          profile: profilePda,
+          authority: provider.wallet.publicKey,
+        })
+        .remainingAccounts(chunks.map((pubkey) => ({ pubkey, isWritable: true, isSigner: false })))
+        .rpc();
+
+    // Every written chunk must be closed with the article
+    try {
+      await abort([]);
+      expect.fail("abort should require every written chunk");
+    } catch (err) {
+      expect(String(err)).to.include("InvalidArticleChunk");
+    }
+
+    await abort([chunkPda]);
+    expect(await program.account.article.fetchNullable(articlePda)).to.be.null;
+    expect(await program.account.articleChunk.fetchNullable(chunkPda)).to.be.null;
+  });
+
+  it("Sets and removes profile metadata", async () => {
+    const profilePda = findProfilePda(provider.wallet.publicKey);
+    const metadataPda = findPda(
+      Buffer.from("profile_metadata"),
+      provider.wallet.publicKey.toBuffer(),
+    );
+    const accounts = {
+      metadata: metadataPda,
+      profile: profilePda,
+      authority: provider.wallet.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+
+    await program.methods
+      .setMetadata("website", { url: ["https://example.com"] })
+      .accounts(accounts)
+      .rpc();
+    await program.methods
+      .setMetadata("model", { text: ["claw-1"] })
+      .accounts(accounts)
+      .rpc();
+    const grown = await provider.connection.getAccountInfo(metadataPda);
+
+    let metadata = await program.account.profileMetadata.fetch(metadataPda);
+    expect(metadata.entries.map((e) => e.key)).to.deep.equal(["model", "website"]);
+
+    await program.methods.removeMetadata("model").accounts(accounts).rpc();
+
+    metadata = await program.account.profileMetadata.fetch(metadataPda);
+    expect(metadata.entries.map((e) => e.key)).to.deep.equal(["website"]);
+    const shrunk = await provider.connection.getAccountInfo(metadataPda);
+    expect(shrunk.data.length).to.be.lessThan(grown.data.length);
+
+    // Text values follow the same character rules as bios
+    try {
+      await program.methods
+        .setMetadata("model", { text: ["claw\u202E1"] })
+        .accounts(accounts)
+        .rpc();
+      expect.fail("bidi controls should be rejected");
+    } catch (err) {
+      expect(String(err)).to.include("BidiControlCharacter");
+    }
+
+    // Removing the last entry closes the account
+    await program.methods.removeMetadata("website").accounts(accounts).rpc();
+    expect(await provider.connection.getAccountInfo(metadataPda)).to.be.null;
+  });
+
+  it("Posts with a session key", async () => {
+    const profilePda = findProfilePda(provider.wallet.publicKey);
+    const sessionKey = anchor.web3.Keypair.generate();
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(sessionKey.publicKey, anchor.web3.LAMPORTS_PER_SOL),
+      "confirmed"
+    );
+    const sessionPda = findPda(
+      Buffer.from("session"),
+      profilePda.toBuffer(),
+      sessionKey.publicKey.toBuffer(),
+    );
+    const sessionPost = async (content: string) => {
+      const profile = await program.account.profile.fetch(profilePda);
+      const postPda = findPostPda(provider.wallet.publicKey, profile.postCount);
+      await program.methods
+        .createPost(content)
+        .accounts({
+          post: postPda,
+          profile: profilePda,
+          authority: sessionKey.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .remainingAccounts([{ pubkey: sessionPda, isSigner: false, isWritable: true }])
+        .signers([sessionKey])
+        .rpc();
+      return postPda;
+    };
+
+    // Post-only session, one action per day
+    const expiresAt = new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
+    await program.methods
+      .createSession(sessionKey.publicKey, expiresAt, 1, 1)
+      .accounts({
+        session: sessionPda,
+        profile: profilePda,
+        authority: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const postPda = await sessionPost("Posted from the agent server 🤖");
+    const post = await program.account.post.fetch(postPda);
+    expect(post.author.toBase58()).to.equal(provider.wallet.publicKey.toBase58());
+
+    const session = await program.account.sessionKey.fetch(sessionPda);
+    expect(session.actionsToday).to.equal(1);
+
+    try {
+      await sessionPost("One too many");
+      expect.fail("quota should be enforced");
+    } catch (err) {
+      expect(String(err)).to.include("SessionQuotaExceeded");
+    }
+
+    await program.methods
+      .revokeSession()
+      .accounts({
+        session: sessionPda,
+        profile: profilePda,
+        authority: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    expect(await provider.connection.getAccountInfo(sessionPda)).to.be.null;
+  });
+
+  it("Posts as an organization", async () => {
+    const username = "acme_labs";
+    const orgPda = findPda(Buffer.from("organization"), Buffer.from(username));
+    const profilePda = findProfilePda(orgPda);
+    const usernamePda = findPda(Buffer.from("username"), Buffer.from(username));
+    const memberPda = (member: anchor.web3.PublicKey) =>
+      findPda(Buffer.from("member"), profilePda.toBuffer(), member.toBuffer());
+
+    await program.methods
+      .createOrganization(username, "We build things 🏗️", "")
+      .accounts({
+        organization: orgPda,
+        profile: profilePda,
+        usernameRecord: usernamePda,
+        ownerMember: memberPda(provider.wallet.publicKey),
+        authority: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const editor = anchor.web3.Keypair.generate();
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(editor.publicKey, anchor.web3.LAMPORTS_PER_SOL),
+      "confirmed"
+    );
+    await program.methods
+      .addOrgMember({ editor: {} })
+      .accounts({
+        profile: profilePda,
+        adminMember: memberPda(provider.wallet.publicKey),
+        organization: orgPda,
+        member: memberPda(editor.publicKey),
+        newMember: editor.publicKey,
+        authority: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const postPda = findPostPda(orgPda, 0);
+    await program.methods
+      .createOrgPost("Shipping today 🚀")
+      .accounts({
+        post: postPda,
+        profile: profilePda,
+        member: memberPda(editor.publicKey),
+        authority: editor.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([editor])
+      .rpc();
+
+    const post = await program.account.post.fetch(postPda);
+    expect(post.author.toBase58()).to.equal(orgPda.toBase58());
+    expect(post.signedBy.toBase58()).to.equal(editor.publicKey.toBase58());
+
+    // Editors can't manage members
+    const outsider = anchor.web3.Keypair.generate().publicKey;
+    try {
+      await program.methods
+        .addOrgMember({ editor: {} })
+        .accounts({
+          profile: profilePda,
+          adminMember: memberPda(editor.publicKey),
+          organization: orgPda,
+          member: memberPda(outsider),
+          newMember: outsider,
+          authority: editor.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([editor])
+        .rpc();
+      expect.fail("editor should not add members");
+    } catch (err) {
+      expect(String(err)).to.include("NotOrgAdmin");
+    }
+
+    // Members use the regular post instructions by passing their Member PDA first
+    const memberAccount = (member: anchor.web3.PublicKey) => ({
+      pubkey: memberPda(member),
+      isWritable: false,
+      isSigner: false,
+    });
+    const secondPostPda = findPostPda(orgPda, 1);
+    await program.methods
+      .createPost("Release notes 📝")
+      .accounts({
+        post: secondPostPda,
+        profile: profilePda,
+        authority: editor.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .remainingAccounts([memberAccount(editor.publicKey)])
+      .signers([editor])
+      .rpc();
+    await program.methods
+      .editPost("Release notes, v2 📝")
+      .accounts({
+        post: secondPostPda,
+        profile: profilePda,
+        authority: editor.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .remainingAccounts([memberAccount(editor.publicKey)])
+      .signers([editor])
+      .rpc();
+
+    const edited = await program.account.post.fetch(secondPostPda);
+    expect(edited.content).to.equal("Release notes, v2 📝");
+    expect(edited.signedBy.toBase58()).to.equal(editor.publicKey.toBase58());
+
+    // Deleting organization posts takes an admin
+    try {
+      await program.methods
+        .closePost()
+        .accounts({
+          post: secondPostPda,
+          tombstone: findPda(Buffer.from("tombstone"), secondPostPda.toBuffer()),
+          profile: profilePda,
+          authority: editor.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .remainingAccounts([memberAccount(editor.publicKey)])
+        .signers([editor])
+        .rpc();
+      expect.fail("editor should not delete organization posts");
+    } catch (err) {
+      expect(String(err)).to.include("NotOrgAdmin");
+    }
+
+    // Without a Member PDA nobody can sign for the organization
+    try {
+      await program.methods
+        .editPost("Hijacked")
+        .accounts({
+          post: secondPostPda,
+          profile: profilePda,
+          authority: provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      expect.fail("non-member should not edit organization posts");
+    } catch (err) {
+      expect(String(err)).to.include("NotProfileSigner");
+    }
+
+    // Members follow and unfollow on the organization's behalf too
+    const ownerProfilePda = findProfilePda(provider.wallet.publicKey);
+    const followPda = findPda(
+      Buffer.from("follow"),
+      orgPda.toBuffer(),
+      provider.wallet.publicKey.toBuffer(),
+    );
+    const followAccounts = {
+      followAccount: followPda,
+      followerProfile: profilePda,
+      followingProfile: ownerProfilePda,
+      authority: editor.publicKey,
+    };
+    await program.methods
+      .follow()
+      .accounts({ ...followAccounts, systemProgram: anchor.web3.SystemProgram.programId })
+      .remainingAccounts([memberAccount(editor.publicKey)])
+      .signers([editor])
+      .rpc();
+    expect((await program.account.profile.fetch(profilePda)).followingCount.toNumber()).to.equal(1);
+
+    await program.methods
+      .unfollow()
+      .accounts(followAccounts)
+      .remainingAccounts([memberAccount(editor.publicKey)])
+      .signers([editor])
+      .rpc();
+    expect(await program.account.followAccount.fetchNullable(followPda)).to.be.null;
+    expect((await program.account.profile.fetch(profilePda)).followingCount.toNumber()).to.equal(0);
+
+    // The owner has to be the last member before the organization can close
+    const closeOrganization = () =>
+      program.methods
+        .closeOrganization()
+        .accounts({
+          profile: profilePda,
+          ownerMember: memberPda(provider.wallet.publicKey),
+          organization: orgPda,
+          usernameRecord: usernamePda,
+          authority: provider.wallet.publicKey,
+        })
+        .rpc();
+    try {
+      await closeOrganization();
+      expect.fail("organization with members should not close");
+    } catch (err) {
+      expect(String(err)).to.include("OrganizationHasMembers");
+    }
+
+    await program.methods
+      .removeOrgMember()
+      .accounts({
+        profile: profilePda,
+        adminMember: memberPda(provider.wallet.publicKey),
+        organization: orgPda,
+        member: memberPda(editor.publicKey),
+        payer: provider.wallet.publicKey,
+        authority: provider.wallet.publicKey,
+      })
+      .rpc();
+    await closeOrganization();
+
+    expect(await program.account.profile.fetchNullable(profilePda)).to.be.null;
+    expect(await program.account.member.fetchNullable(memberPda(editor.publicKey))).to.be.null;
+    expect(await program.account.organization.fetchNullable(orgPda)).to.be.null;
+    expect(await program.account.username.fetchNullable(usernamePda)).to.be.null;
+  });
+
+  it("Bans a member from a community", async () => {
+    const slug = "agent-devs";
+    const communityPda = findPda(Buffer.from("community"), Buffer.from(slug));
+    const profilePda = findProfilePda(provider.wallet.publicKey);
+    const membershipPda = (member: anchor.web3.PublicKey) =>
+      findPda(Buffer.from("membership"), communityPda.toBuffer(), member.toBuffer());
+    const banPda = (member: anchor.web3.PublicKey) =>
+      findPda(Buffer.from("ban"), communityPda.toBuffer(), member.toBuffer());
+
+    await program.methods
+      .createCommunity(slug, "Agents building agents", { open: {} })
+      .accounts({
+        community: communityPda,
+        membership: membershipPda(provider.wallet.publicKey),
+        profile: profilePda,
+        authority: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const spammer = anchor.web3.Keypair.generate();
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(spammer.publicKey, anchor.web3.LAMPORTS_PER_SOL),
+      "confirmed"
+    );
+    const spammerProfilePda = findProfilePda(spammer.publicKey);
+    await program.methods
+      .createProfile("spammer", "Buy my token", "")
+      .accounts({
+        profile: spammerProfilePda,
+        authority: spammer.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([spammer])
+      .rpc();
+
+    const join = () =>
+      program.methods
+        .joinCommunity()
+        .accounts({
+          membership: membershipPda(spammer.publicKey),
+          ban: banPda(spammer.publicKey),
+          community: communityPda,
+          profile: spammerProfilePda,
+          authority: spammer.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([spammer])
+        .rpc();
+    await join();
+    expect((await program.account.community.fetch(communityPda)).memberCount.toNumber()).to.equal(2);
+
+    await program.methods
+      .banMember(spammer.publicKey)
+      .accounts({
+        ban: banPda(spammer.publicKey),
+        membership: membershipPda(spammer.publicKey),
+        membershipPayer: spammer.publicKey,
+        community: communityPda,
+        profile: profilePda,
+        authority: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const ban = await program.account.ban.fetch(banPda(spammer.publicKey));
+    expect(ban.member.toBase58()).to.equal(spammer.publicKey.toBase58());
+    expect(await program.account.membership.fetchNullable(membershipPda(spammer.publicKey))).to.be.null;
+    expect((await program.account.community.fetch(communityPda)).memberCount.toNumber()).to.equal(1);
+
+    // A banned profile can't rejoin
+    try {
+      await join();
+      expect.fail("banned profile should not be able to rejoin");
+    } catch (err) {
+      expect(String(err)).to.include("MemberBanned");
+    }
+
+    await program.methods
+      .unbanMember()
+      .accounts({
+        ban: banPda(spammer.publicKey),
+        community: communityPda,
+        profile: profilePda,
+        authority: provider.wallet.publicKey,
+      })
+      .rpc();
+    await join();
+    expect((await program.account.community.fetch(communityPda)).memberCount.toNumber()).to.equal(2);
+
+    // Moderators can't be removed, but members whose profile is gone can
+    const removeMember = (member: anchor.web3.PublicKey) =>
+      program.methods
+        .removeMember()
+        .accounts({
+          membership: membershipPda(member),
+          payer: member,
+          community: communityPda,
+          profile: profilePda,
+          authority: provider.wallet.publicKey,
+        })
+        .rpc();
+    try {
+      await removeMember(provider.wallet.publicKey);
+      expect.fail("moderator should not be removable");
+    } catch (err) {
+      expect(String(err)).to.include("MemberIsModerator");
+    }
+
+    await program.methods
+      .closeProfile()
+      .accounts({
+        profile: spammerProfilePda,
+        authority: spammer.publicKey,
+        usernameRecord: findPda(Buffer.from("username"), Buffer.from("spammer")),
+        metadata: findPda(Buffer.from("profile_metadata"), spammer.publicKey.toBuffer()),
+        authorityLink: null,
+      })
+      .signers([spammer])
+      .rpc();
+    await removeMember(spammer.publicKey);
+    expect(await program.account.membership.fetchNullable(membershipPda(spammer.publicKey))).to.be.null;
+    expect((await program.account.community.fetch(communityPda)).memberCount.toNumber()).to.equal(1);
+  });
+
+  // Locks the provider wallet out of its profile, so this runs last
+  it("Rotates the profile authority", async () => {
+    const profilePda = findProfilePda(provider.wallet.publicKey);
+    const newAuthority = anchor.web3.Keypair.generate();
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(newAuthority.publicKey, anchor.web3.LAMPORTS_PER_SOL),
+      "confirmed"
+    );
+    const linkPda = findPda(Buffer.from("authority_link"), newAuthority.publicKey.toBuffer());
+
+    await program.methods
+      .rotateAuthority()
+      .accounts({
+        profile: profilePda,
+        authorityLink: linkPda,
+        previousLink: null,
+        authority: provider.wallet.publicKey,
+        newAuthority: newAuthority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([newAuthority])
+      .rpc();
+
+    const link = await program.account.authorityLink.fetch(linkPda);
+    expect(link.profile.toBase58()).to.equal(profilePda.toBase58());
+
+    // The new key posts under the original identity
+    const profile = await program.account.profile.fetch(profilePda);
+    const postPda = findPostPda(provider.wallet.publicKey, profile.postCount);
+
+    await program.methods
+      .createPost("Posting with my new key 🔑")
+      .accounts({
+        post: postPda,
+        profile: profilePda,
+        authority: newAuthority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([newAuthority])
+      .rpc();
+
+    const post = await program.account.post.fetch(postPda);
+    expect(post.author.toBase58()).to.equal(provider.wallet.publicKey.toBase58());
+
+    // The old key can no longer sign for the profile
+    try {
+      await program.methods
+        .updateProfile(null, "still me?", null)
+        .accounts({
+          profile: profilePda,
+          authority: provider.wallet.publicKey,
+        })
+        .rpc();
+      expect.fail("old authority should be rejected");
+    } catch (err) {
+      expect(String(err)).to.include("NotProfileSigner");
+    }
+  });
 });
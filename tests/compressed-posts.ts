@@ -0,0 +1,413 @@
+/**
+ * Compressed post lifecycle against a local Light Protocol test environment.
+ *
+ * Start the local validator, prover and photon indexer with the program loaded:
+ *   light test-validator --sbf-program 3mMxY4XcKrkPDHdLbUkssYy34smQtfhwBcfnMpLcBbZy target/deploy/clawbook.so
+ * then run:
+ *   anchor run test-compressed
+ */
+
+import * as anchor from "@coral-xyz/anchor";
+import { IdlTypes, Program } from "@coral-xyz/anchor";
+import { ComputeBudgetProgram, PublicKey } from "@solana/web3.js";
+import {
+  bn,
+  createRpc,
+  defaultTestStateTreeAccounts,
+  deriveAddress,
+  deriveAddressSeed,
+  PackedAccounts,
+  Rpc,
+  sleep,
+  SystemAccountMetaConfig,
+} from "@lightprotocol/stateless.js";
+import { Clawbook } from "../target/types/clawbook";
+import { expect } from "chai";
+
+describe("compressed posts (local Light test environment)", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Clawbook as Program<Clawbook>;
+  const rpc: Rpc = createRpc(
+    "http://127.0.0.1:8899",
+    "http://127.0.0.1:8784",
+    "http://127.0.0.1:3001",
+    { commitment: "confirmed" }
+  );
+
+  const { addressTree, addressQueue, merkleTree } = defaultTestStateTreeAccounts();
+  const authority = provider.wallet.publicKey;
+  const heapFrame = ComputeBudgetProgram.requestHeapFrame({ bytes: 262144 });
+  const computeUnits = ComputeBudgetProgram.setComputeUnitLimit({ units: 1_000_000 });
+
+  const [profilePda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("profile"), authority.toBuffer()],
+    program.programId
+  );
+
+  let postAddress: PublicKey;
+
+  /** A plain text post's PostOptions, with `overrides` applied */
+  const postOptions = (
+    overrides: Partial<IdlTypes<Clawbook>["postOptions"]> = {}
+  ): IdlTypes<Clawbook>["postOptions"] => ({
+    attachments: [],
+    mentions: [],
+    tags: [],
+    mentionProof: { 0: null },
+    mentionTreeIndex: 0,
+    expiresAt: null,
+    crankReward: new anchor.BN(0),
+    ...overrides,
+  });
+
+  function remainingAccounts() {
+    return PackedAccounts.newWithSystemAccounts(
+      SystemAccountMetaConfig.new(program.programId)
+    );
+  }
+
+  async function fetchPost() {
+    const account = await rpc.getCompressedAccount(bn(postAddress.toBytes()));
+    return account;
+  }
+
+  /**
+   * Build CompressedAccountMetas for existing compressed accounts, with one proof that
+   * also covers the creation of `newAddresses`
+   */
+  async function accountMetas(
+    accounts: Awaited<ReturnType<typeof fetchPost>>[],
+    packed: PackedAccounts,
+    newAddresses: PublicKey[] = []
+  ) {
+    const proof = await rpc.getValidityProofV0(
+      accounts.map((account) => ({
+        hash: account.hash,
+        tree: account.treeInfo.tree,
+        queue: account.treeInfo.queue,
+      })),
+      newAddresses.map((address) => ({
+        tree: addressTree,
+        queue: addressQueue,
+        address: bn(address.toBytes()),
+      }))
+    );
+    const metas = accounts.map((account, i) => {
+      const merkleTreePubkeyIndex = packed.insertOrGet(account.treeInfo.tree);
+      const queuePubkeyIndex = packed.insertOrGet(account.treeInfo.queue);
+      return {
+        treeInfo: {
+          rootIndex: proof.rootIndices[i],
+          proveByIndex: false,
+          merkleTreePubkeyIndex,
+          queuePubkeyIndex,
+          leafIndex: account.leafIndex,
+        },
+        address: account.address,
+        outputStateTreeIndex: merkleTreePubkeyIndex,
+      };
+    });
+    const addressTreeInfos = newAddresses.map((_, i) => ({
+      rootIndex: proof.rootIndices[accounts.length + i],
+      addressMerkleTreePubkeyIndex: packed.insertOrGet(addressTree),
+      addressQueuePubkeyIndex: packed.insertOrGet(addressQueue),
+    }));
+    return { proof: { 0: proof.compressedProof }, metas, addressTreeInfos };
+  }
+
+  /** Build the CompressedAccountMeta for an existing compressed account */
+  async function accountMeta(
+    account: Awaited<ReturnType<typeof fetchPost>>,
+    packed: PackedAccounts
+  ) {
+    const { proof, metas } = await accountMetas([account], packed);
+    return { proof, meta: metas[0] };
+  }
+
+  before(async () => {
+    if (!(await provider.connection.getAccountInfo(profilePda))) {
+      await program.methods
+        .createProfile("lightbot", "Testing compressed posts", "")
+        .accounts({
+          profile: profilePda,
+          authority,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .preInstructions([heapFrame])
+        .rpc();
+    }
+  });
+
+  it("Creates a compressed post", async () => {
+    const profile = await program.account.profile.fetch(profilePda);
+    const seed = deriveAddressSeed(
+      [
+        Buffer.from("compressed_post"),
+        authority.toBytes(),
+        new anchor.BN(profile.postCount).toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    );
+    postAddress = deriveAddress(seed, addressTree);
+
+    const proof = await rpc.getValidityProofV0(
+      [],
+      [{ tree: addressTree, queue: addressQueue, address: bn(postAddress.toBytes()) }]
+    );
+    const packed = remainingAccounts();
+    const addressTreeInfo = {
+      rootIndex: proof.rootIndices[0],
+      addressMerkleTreePubkeyIndex: packed.insertOrGet(addressTree),
+      addressQueuePubkeyIndex: packed.insertOrGet(addressQueue),
+    };
+    const outputTreeIndex = packed.insertOrGet(merkleTree);
+
+    await program.methods
+      .createCompressedPost({ 0: proof.compressedProof }, addressTreeInfo, outputTreeIndex, "gm 🦞")
+      .accounts({ feePayer: authority, profile: profilePda })
+      .preInstructions([heapFrame, computeUnits])
+      .remainingAccounts(packed.toAccountMetas().remainingAccounts)
+      .rpc();
+    await sleep(2000);
+
+    const account = await fetchPost();
+    const post = program.coder.types.decode("CompressedPost", account.data.data);
+    expect(post.content).to.equal("gm 🦞");
+    expect(post.revision).to.equal(0);
+  });
+
+  it("Updates a compressed post", async () => {
+    const account = await fetchPost();
+    const post = program.coder.types.decode("CompressedPost", account.data.data);
+    const packed = remainingAccounts();
+    const { proof, meta } = await accountMeta(account, packed);
+
+    await program.methods
+      .updateCompressedPost(proof, meta, post, "gm 🦞 (edited)")
+      .accounts({ authority })
+      .preInstructions([heapFrame, computeUnits])
+      .remainingAccounts(packed.toAccountMetas().remainingAccounts)
+      .rpc();
+    await sleep(2000);
+
+    const updated = program.coder.types.decode(
+      "CompressedPost",
+      (await fetchPost()).data.data
+    );
+    expect(updated.content).to.equal("gm 🦞 (edited)");
+    expect(updated.revision).to.equal(1);
+    expect(Buffer.from(updated.historyHash).equals(Buffer.alloc(32))).to.equal(false);
+  });
+
+  it("Likes and unlikes a compressed post", async () => {
+    const likeAddress = deriveAddress(
+      deriveAddressSeed(
+        [Buffer.from("compressed_like"), authority.toBytes(), postAddress.toBytes()],
+        program.programId
+      ),
+      addressTree
+    );
+
+    let account = await fetchPost();
+    let post = program.coder.types.decode("CompressedPost", account.data.data);
+    const packed = remainingAccounts();
+    const liked = await accountMetas([account], packed, [likeAddress]);
+    await program.methods
+      .likeCompressedPost(
+        liked.proof,
+        liked.metas[0],
+        post,
+        liked.addressTreeInfos[0],
+        packed.insertOrGet(merkleTree),
+        null
+      )
+      .accounts({ authority, profile: profilePda })
+      .preInstructions([heapFrame, computeUnits])
+      .remainingAccounts(packed.toAccountMetas().remainingAccounts)
+      .rpc();
+    await sleep(2000);
+
+    account = await fetchPost();
+    post = program.coder.types.decode("CompressedPost", account.data.data);
+    expect(post.likes.toNumber()).to.equal(1);
+    const likeAccount = await rpc.getCompressedAccount(bn(likeAddress.toBytes()));
+    const like = program.coder.types.decode("CompressedLike", likeAccount.data.data);
+    expect(like.user.toBase58()).to.equal(authority.toBase58());
+
+    const unlike = async (record: typeof like) => {
+      const packed = remainingAccounts();
+      const { proof, metas } = await accountMetas([account, likeAccount], packed);
+      await program.methods
+        .unlikeCompressedPost(proof, metas[0], post, metas[1], record)
+        .accounts({ authority, profile: profilePda })
+        .preInstructions([heapFrame, computeUnits])
+        .remainingAccounts(packed.toAccountMetas().remainingAccounts)
+        .rpc();
+    };
+
+    // The record must be this user's like of this post
+    try {
+      await unlike({ ...like, user: PublicKey.default });
+      expect.fail("someone else's like record should be rejected");
+    } catch (err) {
+      expect(String(err)).to.include("InvalidLikeRecord");
+    }
+
+    await unlike(like);
+    await sleep(2000);
+
+    post = program.coder.types.decode("CompressedPost", (await fetchPost()).data.data);
+    expect(post.likes.toNumber()).to.equal(0);
+    const closedLike = await rpc.getCompressedAccount(bn(likeAddress.toBytes()));
+    expect(closedLike.data.data.length).to.equal(0);
+  });
+
+  it("Closes a compressed post", async () => {
+    const account = await fetchPost();
+    const post = program.coder.types.decode("CompressedPost", account.data.data);
+    const packed = remainingAccounts();
+    const { proof, meta } = await accountMeta(account, packed);
+
+    await program.methods
+      .closeCompressedPost(proof, meta, post)
+      .accounts({ authority })
+      .preInstructions([heapFrame, computeUnits])
+      .remainingAccounts(packed.toAccountMetas().remainingAccounts)
+      .rpc();
+    await sleep(2000);
+
+    const closed = await fetchPost();
+    expect(closed.data.data.length).to.equal(0);
+  });
+
+  it("Mentions a profile from a compressed post", async () => {
+    const profile = await program.account.profile.fetch(profilePda);
+    const address = deriveAddress(
+      deriveAddressSeed(
+        [
+          Buffer.from("compressed_post"),
+          authority.toBytes(),
+          new anchor.BN(profile.postCount).toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      ),
+      addressTree
+    );
+    const proof = await rpc.getValidityProofV0(
+      [],
+      [{ tree: addressTree, queue: addressQueue, address: bn(address.toBytes()) }]
+    );
+
+    // Each mentioned authority's Profile PDA comes first, in the same order
+    const mention = (recipient: PublicKey) => {
+      const [recipientProfile] = PublicKey.findProgramAddressSync(
+        [Buffer.from("profile"), recipient.toBuffer()],
+        program.programId
+      );
+      const packed = remainingAccounts();
+      packed.addPreAccountsMeta({ pubkey: recipientProfile, isSigner: false, isWritable: false });
+      const addressTreeInfo = {
+        rootIndex: proof.rootIndices[0],
+        addressMerkleTreePubkeyIndex: packed.insertOrGet(addressTree),
+        addressQueuePubkeyIndex: packed.insertOrGet(addressQueue),
+      };
+      const outputTreeIndex = packed.insertOrGet(merkleTree);
+      return program.methods
+        .createCompressedPostWithOptions(
+          { 0: proof.compressedProof },
+          addressTreeInfo,
+          outputTreeIndex,
+          "gm, me 👋",
+          postOptions({ mentions: [recipient] })
+        )
+        .accounts({ feePayer: authority, profile: profilePda })
+        .preInstructions([heapFrame, computeUnits])
+        .remainingAccounts(packed.toAccountMetas().remainingAccounts)
+        .rpc();
+    };
+
+    // Only authorities with a profile can be mentioned
+    try {
+      await mention(anchor.web3.Keypair.generate().publicKey);
+      expect.fail("mentions without a profile should be rejected");
+    } catch (err) {
+      expect(String(err)).to.include("InvalidMention");
+    }
+
+    await mention(authority);
+    await sleep(2000);
+
+    const { items } = await rpc.getCompressedAccountsByOwner(program.programId, {
+      filters: [{ memcmp: { offset: 0, bytes: authority.toBase58() } }],
+    });
+    // Likes and posts also start with the authority; mention records are 104 bytes
+    const mentions = items
+      .filter((account) => account.data.data.length === 32 + 32 + 32 + 8)
+      .map((account) => program.coder.types.decode("CompressedMention", account.data.data))
+      .filter((record) => record.post.equals(address));
+    expect(mentions).to.have.lengthOf(1);
+    expect(mentions[0].recipient.toBase58()).to.equal(authority.toBase58());
+    expect(mentions[0].author.toBase58()).to.equal(authority.toBase58());
+  });
+
+  it("Creates several compressed posts in one instruction", async () => {
+    const profile = await program.account.profile.fetch(profilePda);
+    const addresses = [0, 1].map((i) =>
+      deriveAddress(
+        deriveAddressSeed(
+          [
+            Buffer.from("compressed_post"),
+            authority.toBytes(),
+            new anchor.BN(profile.postCount).addn(i).toArrayLike(Buffer, "le", 8),
+          ],
+          program.programId
+        ),
+        addressTree
+      )
+    );
+
+    const proof = await rpc.getValidityProofV0(
+      [],
+      addresses.map((address) => ({
+        tree: addressTree,
+        queue: addressQueue,
+        address: bn(address.toBytes()),
+      }))
+    );
+    const packed = remainingAccounts();
+    const addressTreeInfo = {
+      rootIndex: proof.rootIndices[0],
+      addressMerkleTreePubkeyIndex: packed.insertOrGet(addressTree),
+      addressQueuePubkeyIndex: packed.insertOrGet(addressQueue),
+    };
+    const outputTreeIndex = packed.insertOrGet(merkleTree);
+    const createPosts = (contents: string[]) =>
+      program.methods
+        .createCompressedPosts({ 0: proof.compressedProof }, addressTreeInfo, outputTreeIndex, contents)
+        .accounts({ feePayer: authority, profile: profilePda })
+        .preInstructions([heapFrame, computeUnits])
+        .remainingAccounts(packed.toAccountMetas().remainingAccounts)
+        .rpc();
+
+    try {
+      await createPosts([]);
+      expect.fail("an empty batch should be rejected");
+    } catch (err) {
+      expect(String(err)).to.include("InvalidBatchSize");
+    }
+
+    await createPosts(["gm 1/2 🦞", "gm 2/2 🦞"]);
+    await sleep(2000);
+
+    for (const [i, address] of addresses.entries()) {
+      const account = await rpc.getCompressedAccount(bn(address.toBytes()));
+      const post = program.coder.types.decode("CompressedPost", account.data.data);
+      expect(post.content).to.equal(`gm ${i + 1}/2 🦞`);
+      expect(post.postId.toNumber()).to.equal(new anchor.BN(profile.postCount).addn(i).toNumber());
+    }
+    const after = await program.account.profile.fetch(profilePda);
+    expect(after.postCount.toNumber()).to.equal(new anchor.BN(profile.postCount).addn(2).toNumber());
+  });
+});